@@ -1,13 +1,18 @@
 const {
   SystemProgram, Connection, sendAndConfirmTransaction,
-  TransactionInstruction, Transaction, PublicKey, Account,
+  TransactionInstruction, Transaction, PublicKey,
+  SYSVAR_RENT_PUBKEY, SYSVAR_CLOCK_PUBKEY,
 } = require('@solana/web3.js');
 const soproxABI = require('soprox-abi');
 
-const HELLO_ACCOUNT_SCHEMA = [{
-  key: 'times',
-  type: 'u32'
-}];
+const HELLO_ACCOUNT_SCHEMA = [
+  { key: 'times', type: 'u32' },
+  { key: 'authority', type: 'pub' },
+  { key: 'last_greeter', type: 'pub' },
+  { key: 'last_greeted_at', type: 'i64' },
+  { key: 'message', type: '[char;32]' },
+  { key: 'initialized', type: 'bool' },
+];
 
 
 class Hello {
@@ -16,34 +21,22 @@ class Hello {
     this.connection = new Connection(nodeUrl, 'recent');
   }
 
-  rentHelloAccount = async (payer) => {
-    const account = new Account();
-    // Compute needed space for a hello account
-    const layout = new soproxABI.struct(HELLO_ACCOUNT_SCHEMA);
-    const space = layout.space;
-    // Compute rental fee
-    const lamports = await this.connection.getMinimumBalanceForRentExemption(space);
-    const instruction = SystemProgram.createAccount({
-      fromPubkey: payer.publicKey,
-      newAccountPubkey: account.publicKey,
-      lamports,
-      space,
-      programId: this.programId
-    });
-    const transaction = new Transaction();
-    transaction.add(instruction);
-    // Send transaction
-    await sendAndConfirmTransaction(
-      this.connection, transaction, [payer, account],
-      { skipPreflight: true, commitment: 'recent' }
+  findHelloAddress = async (greeterPublicKey, programId = this.programId) => {
+    // The hello account is derived from the greeter
+    const [helloPublicKey] = await PublicKey.findProgramAddress(
+      [greeterPublicKey.toBuffer()],
+      programId
     );
-    return account;
+    return helloPublicKey.toBase58();
   }
 
   getHello = async (helloAddress) => {
     const helloPublicKey = new PublicKey(helloAddress);
     // Get raw data
-    const { data } = await this.connection.getAccountInfo(helloPublicKey);
+    const info = await this.connection.getAccountInfo(helloPublicKey);
+    // The greeter has not said hello yet
+    if (!info) return null;
+    const { data } = info;
     // Parse data to json
     const layout = new soproxABI.struct(HELLO_ACCOUNT_SCHEMA);
     layout.fromBuffer(data);
@@ -51,20 +44,25 @@ class Hello {
     return layout.value;
   }
 
-  sayHello = async (helloAddress, payer) => {
-    const helloPublicKey = new PublicKey(helloAddress);
+  sayHello = async (text, payer) => {
+    const helloPublicKey = new PublicKey(await this.findHelloAddress(payer.publicKey));
     // Build input
     const layout = new soproxABI.struct(
       [
         { key: 'code', type: 'u8' },
-        { key: 'amount', type: 'u32' }
+        { key: 'amount', type: 'u32' },
+        { key: 'message', type: '[char;32]' }
       ],
-      { code: 0, amount: 1 });
+      { code: 0, amount: 1, message: text.padEnd(32, '\0').slice(0, 32).split('') });
     const data = layout.toBuffer();
     // Build transaction
     const instruction = new TransactionInstruction({
       keys: [
+        { pubkey: payer.publicKey, isSigner: true, isWritable: true },
         { pubkey: helloPublicKey, isSigner: false, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+        { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
       ],
       programId: this.programId,
       data,
@@ -78,6 +76,104 @@ class Hello {
     );
     return txId;
   }
+
+  initialize = async (authorityAddress, payer) => {
+    const helloPublicKey = new PublicKey(await this.findHelloAddress(payer.publicKey));
+    const authorityPublicKey = new PublicKey(authorityAddress);
+    // Build input
+    const layout = new soproxABI.struct(
+      [{ key: 'code', type: 'u8' }],
+      { code: 2 });
+    const data = layout.toBuffer();
+    // Build transaction
+    const instruction = new TransactionInstruction({
+      keys: [
+        { pubkey: payer.publicKey, isSigner: true, isWritable: true },
+        { pubkey: helloPublicKey, isSigner: false, isWritable: true },
+        { pubkey: authorityPublicKey, isSigner: false, isWritable: false },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data,
+    });
+    const transaction = new Transaction();
+    transaction.add(instruction);
+    // Send transaction
+    const txId = await sendAndConfirmTransaction(
+      this.connection, transaction, [payer],
+      { skipPreflight: true, commitment: 'recent' }
+    );
+    return txId;
+  }
+
+  sayHelloTo = async (targetProgramAddress, text, payer) => {
+    const targetProgramId = new PublicKey(targetProgramAddress);
+    // The hello account of the payer in the target program
+    const helloPublicKey = new PublicKey(await this.findHelloAddress(payer.publicKey, targetProgramId));
+    // Build input
+    const layout = new soproxABI.struct(
+      [
+        { key: 'code', type: 'u8' },
+        { key: 'target_program', type: 'pub' },
+        { key: 'amount', type: 'u32' },
+        { key: 'message', type: '[char;32]' }
+      ],
+      {
+        code: 3,
+        target_program: targetProgramId.toBase58(),
+        amount: 1,
+        message: text.padEnd(32, '\0').slice(0, 32).split('')
+      });
+    const data = layout.toBuffer();
+    // Build transaction
+    const instruction = new TransactionInstruction({
+      keys: [
+        { pubkey: payer.publicKey, isSigner: true, isWritable: true },
+        { pubkey: helloPublicKey, isSigner: false, isWritable: true },
+        { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+        { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+        { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
+        { pubkey: targetProgramId, isSigner: false, isWritable: false },
+      ],
+      programId: this.programId,
+      data,
+    });
+    const transaction = new Transaction();
+    transaction.add(instruction);
+    // Send transaction
+    const txId = await sendAndConfirmTransaction(
+      this.connection, transaction, [payer],
+      { skipPreflight: true, commitment: 'recent' }
+    );
+    return txId;
+  }
+
+  reset = async (helloAddress, authority) => {
+    const helloPublicKey = new PublicKey(helloAddress);
+    // Build input
+    const layout = new soproxABI.struct(
+      [{ key: 'code', type: 'u8' }],
+      { code: 1 });
+    const data = layout.toBuffer();
+    // Build transaction
+    const instruction = new TransactionInstruction({
+      keys: [
+        { pubkey: authority.publicKey, isSigner: true, isWritable: false },
+        { pubkey: helloPublicKey, isSigner: false, isWritable: true },
+      ],
+      programId: this.programId,
+      data,
+    });
+    const transaction = new Transaction();
+    transaction.add(instruction);
+    // Send transaction
+    const txId = await sendAndConfirmTransaction(
+      this.connection, transaction, [authority],
+      { skipPreflight: true, commitment: 'recent' }
+    );
+    return txId;
+  }
 }
 
 module.exports = Hello;
\ No newline at end of file
@@ -1,5 +1,4 @@
 const path = require('path');
-const fs = require('fs');
 const { Account } = require('@solana/web3.js');
 const Hello = require('./hello');
 const { nodeUrl } = require('../soprox.config.json');
@@ -27,23 +26,6 @@ const loadPayer = () => {
   }
 }
 
-const loadHelloAddress = async (helloInstance, payer) => {
-  const dir = path.join(__dirname, '../dist/hello-keypair.json');
-  try {
-    // Load the existed account
-    const helloKey = require(dir);
-    const account = new Account(helloKey);
-    return account.publicKey.toBase58();
-  } catch (er) {
-    // Create a new one
-    const account = await helloInstance.rentHelloAccount(payer);
-    // Store it
-    const data = '[' + account.secretKey.toString() + ']';
-    fs.writeFileSync(dir, data, 'utf8');
-    return account.publicKey.toBase58();
-  }
-}
-
 /**
  * Main
  */
@@ -54,17 +36,31 @@ const loadHelloAddress = async (helloInstance, payer) => {
     const hello = new Hello(programAddress, nodeUrl);
     console.log('*** Calling to program:', programAddress);
     console.log('*** Payer:', payer.publicKey.toBase58());
-    // Build account to store the hello data
-    const helloAddress = await loadHelloAddress(hello, payer);
+    // The account storing the payer's hello data
+    const helloAddress = await hello.findHelloAddress(payer.publicKey);
+    console.log('*** Hello account:', helloAddress);
     // Get hello data
     const dataBefore = await hello.getHello(helloAddress);
     console.log('Hello data before a change:', dataBefore);
+    // Open the hello account with the payer as its authority
+    if (!dataBefore) {
+      const txId = await hello.initialize(payer.publicKey.toBase58(), payer);
+      console.log('Initialize hello data (txId):', txId);
+    }
     // Change hello data
-    const txId = await hello.sayHello(helloAddress, payer);
+    const txId = await hello.sayHello('Hello from SoProX', payer);
     console.log('Change hello data (txId):', txId);
     // Get hello data
     const dataAfter = await hello.getHello(helloAddress);
     console.log('Hello data after a change:', dataAfter);
+    // Forward a greeting through CPI, the program greets itself here
+    const cpiTxId = await hello.sayHelloTo(programAddress, 'Hello via CPI', payer);
+    console.log('Forward hello data (txId):', cpiTxId);
+    console.log('Hello data after a forward:', await hello.getHello(helloAddress));
+    // Reset hello data by its authority
+    const resetTxId = await hello.reset(helloAddress, payer);
+    console.log('Reset hello data (txId):', resetTxId);
+    console.log('Hello data after a reset:', await hello.getHello(helloAddress));
   } catch (er) {
     return console.error(er);
   }
@@ -19,6 +19,14 @@ pub enum AppError {
   IncorrectProgramId,
   #[error("Operation overflowed")]
   Overflow,
+  #[error("Invalid owner")]
+  InvalidOwner,
+  #[error("Unmatched hello account")]
+  UnmatchedHello,
+  #[error("Invalid authority")]
+  InvalidAuthority,
+  #[error("Already initialized")]
+  AlreadyInitialized,
 }
 
 impl From<AppError> for ProgramError {
@@ -42,6 +50,10 @@ impl PrintProgramError for AppError {
       AppError::InvalidInstruction => msg!("Error: Invalid instruction"),
       AppError::IncorrectProgramId => msg!("Error: Incorrect program id"),
       AppError::Overflow => msg!("Error: Operation overflowed"),
+      AppError::InvalidOwner => msg!("Error: Invalid owner"),
+      AppError::UnmatchedHello => msg!("Error: Unmatched hello account"),
+      AppError::InvalidAuthority => msg!("Error: Invalid authority"),
+      AppError::AlreadyInitialized => msg!("Error: Already initialized"),
     }
   }
 }
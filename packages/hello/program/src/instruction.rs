@@ -1,10 +1,23 @@
 use crate::error::AppError;
-use solana_program::program_error::ProgramError;
+use crate::schema::hello::{Hello, MESSAGE_LEN};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
 use std::convert::TryInto;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum AppInstruction {
-  SayHello { amount: u32 },
+  SayHello {
+    amount: u32,
+    message: [char; MESSAGE_LEN],
+  },
+  /// Zero the counter, signed by its authority: the one given to Initialize, or else
+  /// whoever greeted first
+  Reset {},
+  Initialize {},
+  SayHelloTo {
+    target_program: Pubkey,
+    amount: u32,
+    message: [char; MESSAGE_LEN],
+  },
 }
 impl AppInstruction {
   pub fn unpack(instruction: &[u8]) -> Result<Self, ProgramError> {
@@ -18,7 +31,34 @@ impl AppInstruction {
           .and_then(|slice| slice.try_into().ok())
           .map(u32::from_le_bytes)
           .ok_or(AppError::InvalidInstruction)?;
-        Self::SayHello { amount }
+        let message = rest
+          .get(4..4 + MESSAGE_LEN * 4)
+          .and_then(Hello::unpack_message)
+          .ok_or(AppError::InvalidInstruction)?;
+        Self::SayHello { amount, message }
+      }
+      1 => Self::Reset {},
+      2 => Self::Initialize {},
+      3 => {
+        let target_program = rest
+          .get(..32)
+          .and_then(|slice| slice.try_into().ok())
+          .map(Pubkey::new_from_array)
+          .ok_or(AppError::InvalidInstruction)?;
+        let amount = rest
+          .get(32..36)
+          .and_then(|slice| slice.try_into().ok())
+          .map(u32::from_le_bytes)
+          .ok_or(AppError::InvalidInstruction)?;
+        let message = rest
+          .get(36..36 + MESSAGE_LEN * 4)
+          .and_then(Hello::unpack_message)
+          .ok_or(AppError::InvalidInstruction)?;
+        Self::SayHelloTo {
+          target_program,
+          amount,
+          message,
+        }
       }
       _ => return Err(AppError::InvalidInstruction.into()),
     })
@@ -0,0 +1,158 @@
+use crate::schema::hello::MESSAGE_LEN;
+use solana_program::{
+  instruction::{AccountMeta, Instruction},
+  program_error::ProgramError,
+  pubkey::Pubkey,
+  system_program, sysvar,
+};
+use std::mem::size_of;
+
+pub struct IHello {}
+
+impl IHello {
+  pub fn say_hello(
+    program_id: Pubkey,
+    greeter: Pubkey,
+    hello_acc: Pubkey,
+    amount: u32,
+    message: [char; MESSAGE_LEN],
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // SayHello - Code 0
+    data.push(0);
+    data.extend_from_slice(&amount.to_le_bytes());
+    for c in message.iter() {
+      data.extend_from_slice(&(*c as u32).to_le_bytes());
+    }
+    // Build accounts
+    let mut accounts = Vec::with_capacity(5);
+    accounts.push(AccountMeta::new(greeter, true));
+    accounts.push(AccountMeta::new(hello_acc, false));
+    accounts.push(AccountMeta::new_readonly(system_program::id(), false));
+    accounts.push(AccountMeta::new_readonly(sysvar::rent::id(), false));
+    accounts.push(AccountMeta::new_readonly(sysvar::clock::id(), false));
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+
+  pub fn reset(
+    program_id: Pubkey,
+    authority: Pubkey,
+    hello_acc: Pubkey,
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // Reset - Code 1
+    data.push(1);
+    // Build accounts
+    let mut accounts = Vec::with_capacity(2);
+    accounts.push(AccountMeta::new_readonly(authority, true));
+    accounts.push(AccountMeta::new(hello_acc, false));
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+
+  pub fn initialize(
+    program_id: Pubkey,
+    greeter: Pubkey,
+    hello_acc: Pubkey,
+    authority: Pubkey,
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // Initialize - Code 2
+    data.push(2);
+    // Build accounts
+    let mut accounts = Vec::with_capacity(5);
+    accounts.push(AccountMeta::new(greeter, true));
+    accounts.push(AccountMeta::new(hello_acc, false));
+    accounts.push(AccountMeta::new_readonly(authority, false));
+    accounts.push(AccountMeta::new_readonly(system_program::id(), false));
+    accounts.push(AccountMeta::new_readonly(sysvar::rent::id(), false));
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+
+  pub fn say_hello_to(
+    program_id: Pubkey,
+    greeter: Pubkey,
+    hello_acc: Pubkey,
+    target_program: Pubkey,
+    amount: u32,
+    message: [char; MESSAGE_LEN],
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // SayHelloTo - Code 3
+    data.push(3);
+    data.extend_from_slice(&target_program.to_bytes());
+    data.extend_from_slice(&amount.to_le_bytes());
+    for c in message.iter() {
+      data.extend_from_slice(&(*c as u32).to_le_bytes());
+    }
+    // Build accounts
+    let mut accounts = Vec::with_capacity(6);
+    accounts.push(AccountMeta::new(greeter, true));
+    accounts.push(AccountMeta::new(hello_acc, false));
+    accounts.push(AccountMeta::new_readonly(system_program::id(), false));
+    accounts.push(AccountMeta::new_readonly(sysvar::rent::id(), false));
+    accounts.push(AccountMeta::new_readonly(sysvar::clock::id(), false));
+    accounts.push(AccountMeta::new_readonly(target_program, false));
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::instruction::AppInstruction;
+
+  #[test]
+  fn say_hello_round_trips() {
+    let program_id = Pubkey::new_unique();
+    let greeter = Pubkey::new_unique();
+    let hello_acc = Pubkey::new_unique();
+    let mut message = ['\0'; MESSAGE_LEN];
+    for (c, m) in message.iter_mut().zip("gm ☀".chars()) {
+      *c = m;
+    }
+    let ix = IHello::say_hello(program_id, greeter, hello_acc, 42, message).unwrap();
+    assert_eq!(ix.program_id, program_id);
+    assert_eq!(
+      AppInstruction::unpack(&ix.data),
+      Ok(AppInstruction::SayHello {
+        amount: 42,
+        message
+      })
+    );
+    // In the order SayHello reads them: greeter, hello account, system program, rent, clock
+    assert_eq!(
+      ix.accounts,
+      vec![
+        AccountMeta::new(greeter, true),
+        AccountMeta::new(hello_acc, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+      ]
+    );
+  }
+}
@@ -1,5 +1,6 @@
 pub mod entrypoint;
 pub mod error;
 pub mod instruction;
+pub mod interfaces;
 pub mod processor;
 pub mod schema;
@@ -1,12 +1,18 @@
 use crate::error::AppError;
 use crate::instruction::AppInstruction;
+use crate::interfaces::ihello::IHello;
 use crate::schema::hello::Hello;
 use solana_program::{
   account_info::{next_account_info, AccountInfo},
+  clock::Clock,
   entrypoint::ProgramResult,
   msg,
-  program_pack::Pack,
+  program::{invoke, invoke_signed},
+  program_pack::{IsInitialized, Pack},
   pubkey::Pubkey,
+  rent::Rent,
+  system_instruction,
+  sysvar::Sysvar,
 };
 
 pub struct Processor {}
@@ -19,18 +25,323 @@ impl Processor {
   ) -> ProgramResult {
     let instruction = AppInstruction::unpack(instruction_data)?;
     match instruction {
-      AppInstruction::SayHello { amount } => {
+      AppInstruction::SayHello { amount, message } => {
         msg!("Calling SayHello function");
         let accounts_iter = &mut accounts.iter();
+        let greeter = next_account_info(accounts_iter)?;
+        let account = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        let sysvar_rent_acc = next_account_info(accounts_iter)?;
+        let sysvar_clock_acc = next_account_info(accounts_iter)?;
+        Self::open(
+          program_id,
+          greeter,
+          account,
+          system_program,
+          sysvar_rent_acc,
+        )?;
+
+        // The account is derived from the greeter, whose signature is all a greeting needs,
+        // the authority only governs Reset
+        let mut data = Hello::unpack_unchecked(&account.data.borrow())?;
+        // Without a prior Initialize, the greeter is the authority of the counter
+        if !data.is_initialized() {
+          data.authority = *greeter.key;
+          data.initialized = true;
+        }
+        let clock = Clock::from_account_info(sysvar_clock_acc)?;
+        data.times = data.times.checked_add(amount).ok_or(AppError::Overflow)?;
+        data.last_greeter = *greeter.key;
+        data.last_greeted_at = clock.unix_timestamp;
+        data.message = message;
+        Hello::pack(data, &mut account.data.borrow_mut())?;
+        Ok(())
+      }
+
+      AppInstruction::Reset {} => {
+        msg!("Calling Reset function");
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
         let account = next_account_info(accounts_iter)?;
         if account.owner != program_id {
           return Err(AppError::IncorrectProgramId.into());
         }
-        let mut data = Hello::unpack(&account.data.borrow())?;
-        data.times = data.times.checked_add(amount).ok_or(AppError::Overflow)?;
+
+        let data = Hello::unpack(&account.data.borrow())?;
+        if !authority.is_signer || data.authority != *authority.key {
+          return Err(AppError::InvalidAuthority.into());
+        }
+        let data = Hello {
+          authority: data.authority,
+          initialized: true,
+          ..Default::default()
+        };
+        Hello::pack(data, &mut account.data.borrow_mut())?;
+        Ok(())
+      }
+
+      AppInstruction::Initialize {} => {
+        msg!("Calling Initialize function");
+        let accounts_iter = &mut accounts.iter();
+        let greeter = next_account_info(accounts_iter)?;
+        let account = next_account_info(accounts_iter)?;
+        let authority = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        let sysvar_rent_acc = next_account_info(accounts_iter)?;
+        Self::open(
+          program_id,
+          greeter,
+          account,
+          system_program,
+          sysvar_rent_acc,
+        )?;
+
+        let mut data = Hello::unpack_unchecked(&account.data.borrow())?;
+        if data.is_initialized() {
+          return Err(AppError::AlreadyInitialized.into());
+        }
+        data.authority = *authority.key;
+        data.initialized = true;
         Hello::pack(data, &mut account.data.borrow_mut())?;
         Ok(())
       }
+
+      AppInstruction::SayHelloTo {
+        target_program,
+        amount,
+        message,
+      } => {
+        msg!("Calling SayHelloTo function");
+        let accounts_iter = &mut accounts.iter();
+        let greeter = next_account_info(accounts_iter)?;
+        let account = next_account_info(accounts_iter)?; // The hello account in the target program
+        let system_program = next_account_info(accounts_iter)?;
+        let sysvar_rent_acc = next_account_info(accounts_iter)?;
+        let sysvar_clock_acc = next_account_info(accounts_iter)?;
+        let target_program_acc = next_account_info(accounts_iter)?;
+        if *target_program_acc.key != target_program || !target_program_acc.executable {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+        if !greeter.is_signer {
+          return Err(AppError::InvalidOwner.into());
+        }
+
+        // The greeter signature is forwarded to the target program
+        let say_hello_ix =
+          IHello::say_hello(target_program, *greeter.key, *account.key, amount, message)?;
+        invoke(
+          &say_hello_ix,
+          &[
+            target_program_acc.clone(),
+            greeter.clone(),
+            account.clone(),
+            system_program.clone(),
+            sysvar_rent_acc.clone(),
+            sysvar_clock_acc.clone(),
+          ],
+        )?;
+        Ok(())
+      }
+    }
+  }
+
+  /// Check the greeter and its hello account, creating the account when missing
+  fn open<'a>(
+    program_id: &Pubkey,
+    greeter: &AccountInfo<'a>,
+    account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    sysvar_rent_acc: &AccountInfo<'a>,
+  ) -> ProgramResult {
+    if !greeter.is_signer {
+      return Err(AppError::InvalidOwner.into());
+    }
+    let (hello_key, bump) = Hello::find_address(greeter.key, program_id);
+    if hello_key != *account.key {
+      return Err(AppError::UnmatchedHello.into());
+    }
+
+    // Anyone may fund the address beforehand, so top it up rather than create it
+    if account.owner != program_id {
+      let rent = Rent::from_account_info(sysvar_rent_acc)?;
+      let seed: &[&[_]] = &[&greeter.key.to_bytes()[..], &[bump]];
+      let required = rent
+        .minimum_balance(Hello::LEN)
+        .saturating_sub(account.lamports());
+      if required > 0 {
+        let transfer_ix = system_instruction::transfer(greeter.key, account.key, required);
+        invoke(
+          &transfer_ix,
+          &[system_program.clone(), greeter.clone(), account.clone()],
+        )?;
+      }
+      let allocate_ix = system_instruction::allocate(account.key, Hello::LEN as u64);
+      invoke_signed(
+        &allocate_ix,
+        &[system_program.clone(), account.clone()],
+        &[seed],
+      )?;
+      let assign_ix = system_instruction::assign(account.key, program_id);
+      invoke_signed(
+        &assign_ix,
+        &[system_program.clone(), account.clone()],
+        &[seed],
+      )?;
+    }
+    if account.owner != program_id {
+      return Err(AppError::IncorrectProgramId.into());
     }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::schema::hello::MESSAGE_LEN;
+  use solana_program::{instruction::Instruction, program_error::ProgramError, sysvar};
+
+  // Run an instruction, each account given by its owner and data in the instruction order
+  fn process(
+    program_id: &Pubkey,
+    ix: &Instruction,
+    accounts: &mut [(Pubkey, Vec<u8>)],
+  ) -> ProgramResult {
+    let mut lamports = vec![0u64; accounts.len()];
+    let infos: Vec<AccountInfo> = ix
+      .accounts
+      .iter()
+      .zip(accounts.iter_mut())
+      .zip(lamports.iter_mut())
+      .map(|((meta, (owner, data)), lamports)| {
+        AccountInfo::new(
+          &meta.pubkey,
+          meta.is_signer,
+          meta.is_writable,
+          lamports,
+          data,
+          owner,
+          false,
+          0,
+        )
+      })
+      .collect();
+    Processor::process(program_id, &infos, &ix.data)
+  }
+
+  // The accounts of an instruction, the hello account already owned by the program
+  fn accounts(program_id: &Pubkey, ix: &Instruction, hello: &[u8]) -> Vec<(Pubkey, Vec<u8>)> {
+    ix.accounts
+      .iter()
+      .enumerate()
+      .map(|(i, meta)| match i {
+        1 => (*program_id, hello.to_vec()),
+        // A clock at slot 0, only its unix timestamp is set, last of its fields
+        _ if meta.pubkey == sysvar::clock::id() => {
+          let mut data = vec![0u8; 40];
+          data[32..].copy_from_slice(&1_600_000_000i64.to_le_bytes());
+          (sysvar::id(), data)
+        }
+        _ => (Pubkey::default(), vec![]),
+      })
+      .collect()
+  }
+
+  // Greet into a hello account, returning the account data left
+  fn say_hello(program_id: &Pubkey, greeter: &Pubkey, hello: &[u8]) -> Vec<u8> {
+    let (hello_acc, _) = Hello::find_address(greeter, program_id);
+    let ix = IHello::say_hello(*program_id, *greeter, hello_acc, 3, ['\0'; MESSAGE_LEN]).unwrap();
+    let mut accounts = accounts(program_id, &ix, hello);
+    assert_eq!(process(program_id, &ix, &mut accounts), Ok(()));
+    accounts.swap_remove(1).1
+  }
+
+  #[test]
+  fn rejects_the_hello_account_of_another_greeter() {
+    let program_id = Pubkey::new_unique();
+    let greeter = Pubkey::new_unique();
+    let (other, _) = Hello::find_address(&Pubkey::new_unique(), &program_id);
+    let ix = IHello::say_hello(program_id, greeter, other, 1, ['\0'; MESSAGE_LEN]).unwrap();
+    let mut accounts: Vec<(Pubkey, Vec<u8>)> = ix
+      .accounts
+      .iter()
+      .map(|_| (Pubkey::default(), vec![]))
+      .collect();
+    assert_eq!(
+      process(&program_id, &ix, &mut accounts),
+      Err(ProgramError::from(AppError::UnmatchedHello))
+    );
+  }
+
+  #[test]
+  fn only_the_first_greeter_may_reset() {
+    let program_id = Pubkey::new_unique();
+    let greeter = Pubkey::new_unique();
+    let (hello_acc, _) = Hello::find_address(&greeter, &program_id);
+    let hello = say_hello(&program_id, &greeter, &[0u8; Hello::LEN]);
+    let hello = say_hello(&program_id, &greeter, &hello);
+    assert_eq!(Hello::unpack(&hello).unwrap().authority, greeter);
+    assert_eq!(Hello::unpack(&hello).unwrap().times, 6);
+
+    let ix = IHello::reset(program_id, Pubkey::new_unique(), hello_acc).unwrap();
+    assert_eq!(
+      process(&program_id, &ix, &mut accounts(&program_id, &ix, &hello)),
+      Err(ProgramError::from(AppError::InvalidAuthority))
+    );
+    let ix = IHello::reset(program_id, greeter, hello_acc).unwrap();
+    let mut reset = accounts(&program_id, &ix, &hello);
+    assert_eq!(process(&program_id, &ix, &mut reset), Ok(()));
+    assert_eq!(
+      Hello::unpack(&reset[1].1).unwrap(),
+      Hello {
+        authority: greeter,
+        initialized: true,
+        ..Hello::default()
+      }
+    );
+  }
+
+  #[test]
+  fn cannot_initialize_after_a_greeting() {
+    let program_id = Pubkey::new_unique();
+    let greeter = Pubkey::new_unique();
+    let (hello_acc, _) = Hello::find_address(&greeter, &program_id);
+    let hello = say_hello(&program_id, &greeter, &[0u8; Hello::LEN]);
+    let ix = IHello::initialize(program_id, greeter, hello_acc, Pubkey::new_unique()).unwrap();
+    assert_eq!(
+      process(&program_id, &ix, &mut accounts(&program_id, &ix, &hello)),
+      Err(ProgramError::from(AppError::AlreadyInitialized))
+    );
+  }
+
+  #[test]
+  fn only_the_initialized_authority_may_reset() {
+    let program_id = Pubkey::new_unique();
+    let greeter = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let (hello_acc, _) = Hello::find_address(&greeter, &program_id);
+    let ix = IHello::initialize(program_id, greeter, hello_acc, authority).unwrap();
+    let mut initialize = accounts(&program_id, &ix, &[0u8; Hello::LEN]);
+    assert_eq!(process(&program_id, &ix, &mut initialize), Ok(()));
+    let hello = say_hello(&program_id, &greeter, &initialize[1].1);
+    assert_eq!(Hello::unpack(&hello).unwrap().authority, authority);
+
+    // Neither the greeter nor an unsigned authority
+    let ix = IHello::reset(program_id, greeter, hello_acc).unwrap();
+    assert_eq!(
+      process(&program_id, &ix, &mut accounts(&program_id, &ix, &hello)),
+      Err(ProgramError::from(AppError::InvalidAuthority))
+    );
+    let mut ix = IHello::reset(program_id, authority, hello_acc).unwrap();
+    ix.accounts[0].is_signer = false;
+    assert_eq!(
+      process(&program_id, &ix, &mut accounts(&program_id, &ix, &hello)),
+      Err(ProgramError::from(AppError::InvalidAuthority))
+    );
+    ix.accounts[0].is_signer = true;
+    assert_eq!(
+      process(&program_id, &ix, &mut accounts(&program_id, &ix, &hello)),
+      Ok(())
+    );
   }
 }
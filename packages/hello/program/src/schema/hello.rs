@@ -1,8 +1,13 @@
-use arrayref::{array_mut_ref, array_ref};
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
 use solana_program::{
   program_error::ProgramError,
   program_pack::{IsInitialized, Pack, Sealed},
+  pubkey::Pubkey,
 };
+use std::convert::TryInto;
+
+/// Maximum number of chars in a greeting message
+pub const MESSAGE_LEN: usize = 32;
 
 //
 // Define the data struct
@@ -10,6 +15,31 @@ use solana_program::{
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct Hello {
   pub times: u32,
+  // May Reset the counter, set by Initialize or else to whoever greeted first
+  pub authority: Pubkey,
+  pub last_greeter: Pubkey,
+  pub last_greeted_at: i64,
+  pub message: [char; MESSAGE_LEN],
+  pub initialized: bool,
+}
+
+impl Hello {
+  /// The hello address of a greeter, one counter per greeter
+  pub fn find_address(greeter: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[&greeter.to_bytes()[..]], program_id)
+  }
+
+  /// Decode a message of utf-32 chars, right padded by '\0'
+  pub fn unpack_message(src: &[u8]) -> Option<[char; MESSAGE_LEN]> {
+    let mut message = ['\0'; MESSAGE_LEN];
+    if src.len() != MESSAGE_LEN * 4 {
+      return None;
+    }
+    for (c, slice) in message.iter_mut().zip(src.chunks(4)) {
+      *c = char::from_u32(u32::from_le_bytes(slice.try_into().ok()?))?;
+    }
+    Some(message)
+  }
 }
 
 //
@@ -22,7 +52,7 @@ impl Sealed for Hello {}
 //
 impl IsInitialized for Hello {
   fn is_initialized(&self) -> bool {
-    true
+    self.initialized
   }
 }
 
@@ -31,17 +61,117 @@ impl IsInitialized for Hello {
 //
 impl Pack for Hello {
   // Fixed length
-  const LEN: usize = 4;
+  const LEN: usize = 4 + 32 + 32 + 8 + MESSAGE_LEN * 4 + 1;
   // Unpack data from [u8] to the data struct
   fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-    let src_times = array_ref![src, 0, 4];
-    let times = u32::from_le_bytes(*src_times);
-    Ok(Hello { times })
+    let src = array_ref![src, 0, 205];
+    let (times, authority, last_greeter, last_greeted_at, message, initialized) =
+      array_refs![src, 4, 32, 32, 8, MESSAGE_LEN * 4, 1];
+    Ok(Hello {
+      times: u32::from_le_bytes(*times),
+      authority: Pubkey::new_from_array(*authority),
+      last_greeter: Pubkey::new_from_array(*last_greeter),
+      last_greeted_at: i64::from_le_bytes(*last_greeted_at),
+      message: Self::unpack_message(message).ok_or(ProgramError::InvalidAccountData)?,
+      initialized: match initialized {
+        [0] => false,
+        [1] => true,
+        _ => return Err(ProgramError::InvalidAccountData),
+      },
+    })
   }
   // Pack data from the data struct to [u8]
   fn pack_into_slice(&self, dst: &mut [u8]) {
-    let dst_times = array_mut_ref![dst, 0, 4];
-    let &Hello { times } = self;
+    let dst = array_mut_ref![dst, 0, 205];
+    let (
+      dst_times,
+      dst_authority,
+      dst_last_greeter,
+      dst_last_greeted_at,
+      dst_message,
+      dst_initialized,
+    ) = mut_array_refs![dst, 4, 32, 32, 8, MESSAGE_LEN * 4, 1];
+    let &Hello {
+      times,
+      ref authority,
+      ref last_greeter,
+      last_greeted_at,
+      ref message,
+      initialized,
+    } = self;
     *dst_times = times.to_le_bytes();
+    dst_authority.copy_from_slice(authority.as_ref());
+    dst_last_greeter.copy_from_slice(last_greeter.as_ref());
+    *dst_last_greeted_at = last_greeted_at.to_le_bytes();
+    for (c, slice) in message.iter().zip(dst_message.chunks_mut(4)) {
+      slice.copy_from_slice(&(*c as u32).to_le_bytes());
+    }
+    *dst_initialized = [initialized as u8];
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn finds_one_address_per_greeter() {
+    let program_id = Pubkey::new_unique();
+    let greeter = Pubkey::new_unique();
+    let (address, bump) = Hello::find_address(&greeter, &program_id);
+    assert_eq!(
+      Pubkey::create_program_address(&[&greeter.to_bytes()[..], &[bump]], &program_id),
+      Ok(address)
+    );
+    assert_eq!(Hello::find_address(&greeter, &program_id), (address, bump));
+    assert_ne!(
+      Hello::find_address(&Pubkey::new_unique(), &program_id).0,
+      address
+    );
+  }
+
+  #[test]
+  fn packs_and_unpacks() {
+    let mut message = ['\0'; MESSAGE_LEN];
+    for (c, m) in message.iter_mut().zip("Hello, world!".chars()) {
+      *c = m;
+    }
+    let hello = Hello {
+      times: 7,
+      authority: Pubkey::new_unique(),
+      last_greeter: Pubkey::new_unique(),
+      last_greeted_at: 1_600_000_000,
+      message,
+      initialized: true,
+    };
+    assert_eq!(Hello::LEN, 205);
+    let mut data = [0u8; Hello::LEN];
+    Hello::pack(hello, &mut data).unwrap();
+    assert_eq!(Hello::unpack(&data).unwrap(), hello);
+  }
+
+  #[test]
+  fn decodes_utf32_messages() {
+    let mut src = vec![0u8; MESSAGE_LEN * 4];
+    for (slice, c) in src.chunks_mut(4).zip("héllo 👋".chars()) {
+      slice.copy_from_slice(&(c as u32).to_le_bytes());
+    }
+    let message = Hello::unpack_message(&src).unwrap();
+    assert_eq!(message[..8], ['h', 'é', 'l', 'l', 'o', ' ', '👋', '\0']);
+    assert!(message[7..].iter().all(|c| *c == '\0'));
+  }
+
+  #[test]
+  fn rejects_invalid_messages() {
+    // A surrogate and a value past the last code point
+    for code in [0xD800u32, 0x110000].iter() {
+      let mut src = vec![0u8; MESSAGE_LEN * 4];
+      src[..4].copy_from_slice(&code.to_le_bytes());
+      assert_eq!(Hello::unpack_message(&src), None);
+    }
+    assert_eq!(Hello::unpack_message(&[0u8; MESSAGE_LEN * 4 - 1]), None);
+    let mut data = [0u8; Hello::LEN];
+    data[76..80].copy_from_slice(&0xD800u32.to_le_bytes());
+    assert_eq!(Hello::unpack(&data), Err(ProgramError::InvalidAccountData));
   }
 }
@@ -0,0 +1,160 @@
+const { sendAndConfirmTransaction, TransactionInstruction, Transaction, Account } = require('@solana/web3.js');
+const soproxABI = require('soprox-abi');
+const { deployRegister } = require('../../lib/network');
+const { init } = require('./helpers');
+
+// AppError::DuplicateAccount
+const DUPLICATE_ACCOUNT = 7;
+
+/**
+ * Send an instruction whose accounts are given by roles
+ */
+const call = async (code, args, roles, programId, payer, connection) => {
+  const schema = [{ key: 'code', type: 'u8' }];
+  const data = { code };
+  args.forEach(({ key, type, value }) => {
+    schema.push({ key, type });
+    data[key] = value;
+  });
+  const layout = new soproxABI.struct(schema, data);
+  const instruction = new TransactionInstruction({
+    keys: roles.map(({ register, isSigner, isWritable }) => ({
+      pubkey: register.publicKey, isSigner, isWritable
+    })),
+    programId,
+    data: layout.toBuffer()
+  });
+  const transaction = new Transaction();
+  transaction.add(instruction);
+  // Collect the distinct signers
+  const signers = [payer];
+  roles.forEach(({ register, isSigner }) => {
+    if (!isSigner || !register.secretKey) return;
+    if (signers.some(signer => signer.publicKey.equals(register.publicKey))) return;
+    signers.push(new Account(Buffer.from(register.secretKey, 'hex')));
+  });
+  await sendAndConfirmTransaction(
+    connection, transaction, signers,
+    {
+      skipPreflight: true,
+      commitment: 'recent',
+    });
+}
+
+/**
+ * Replace the role at index j by the account at index i, expect a DuplicateAccount failure
+ */
+const expectAliasFailure = async (name, code, args, roles, pairs, programId, payer, connection) => {
+  for (const [i, j] of pairs) {
+    const aliased = roles.map((role, k) => k == j ? { ...role, register: roles[i].register } : role);
+    try {
+      await call(code, args, aliased, programId, payer, connection);
+    } catch (er) {
+      if (!er.message.includes(`{"Custom":${DUPLICATE_ACCOUNT}}`))
+        throw new Error(`${name}: #${i} aliasing #${j} failed for another reason: ${er.message}`);
+      console.log(`${name}: #${i} aliasing #${j} rejected`);
+      continue;
+    }
+    throw new Error(`${name}: #${i} aliasing #${j} must be rejected`);
+  }
+}
+
+/**
+ * Create an uninitialized register with the layout of the given one
+ */
+const freshRegister = async (register, programId, payer, connection) => {
+  const space = soproxABI.span(register);
+  const account = await deployRegister(space, payer, programId, connection);
+  return {
+    publicKey: account.publicKey,
+    secretKey: Buffer.from(account.secretKey).toString('hex'),
+  };
+}
+
+/**
+ * The amount argument of most instructions
+ */
+const amount = (value) => [{ key: 'amount', type: 'u64', value }];
+
+/**
+ * All pairs of indices of the given roles
+ */
+const allPairs = (indices) => {
+  const pairs = [];
+  indices.forEach((i, k) => indices.slice(k + 1).forEach(j => pairs.push([i, j])));
+  return pairs;
+}
+
+module.exports = async function () {
+  console.log('\n\n*** Test alias\n');
+  const { connection, payer, programId, registers: [token, source, destination, delegation] } = await init();
+  const owner = { publicKey: payer.publicKey };
+  // Constructors must not fail because their targets are already built
+  const freshToken = await freshRegister(token, programId, payer, connection);
+  const freshReceiver = await freshRegister(source, programId, payer, connection);
+  const freshTarget = await freshRegister(destination, programId, payer, connection);
+
+  // Token constructor: deployer, token, receiver
+  await expectAliasFailure('TokenConstructor', 0, [
+    { key: 'symbol', type: '[char;4]', value: ['A', 'L', 'I', 'S'] },
+    { key: 'totalSupply', type: 'u64', value: 1n },
+    { key: 'decimals', type: 'u8', value: 0 },
+  ], [
+    { register: owner, isSigner: true, isWritable: false },
+    { register: freshToken, isSigner: true, isWritable: true },
+    { register: freshReceiver, isSigner: true, isWritable: true },
+  ], allPairs([0, 1, 2]), programId, payer, connection);
+
+  // Account constructor: caller, token, target
+  await expectAliasFailure('AccountConstructor', 1, [], [
+    { register: owner, isSigner: true, isWritable: false },
+    { register: token, isSigner: false, isWritable: false },
+    { register: freshTarget, isSigner: true, isWritable: true },
+  ], allPairs([0, 1, 2]), programId, payer, connection);
+
+  // Transfer: owner, token, source, destination (source and destination may coincide)
+  await expectAliasFailure('Transfer', 3, amount(1n), [
+    { register: owner, isSigner: true, isWritable: false },
+    { register: token, isSigner: false, isWritable: false },
+    { register: source, isSigner: false, isWritable: true },
+    { register: destination, isSigner: false, isWritable: true },
+  ], [[0, 1], [0, 2], [0, 3], [1, 2], [1, 3]], programId, payer, connection);
+
+  // Approve: owner, token, delegation, source, delegate (the owner may delegate to itself)
+  await expectAliasFailure('Approve', 4, amount(1n), [
+    { register: owner, isSigner: true, isWritable: false },
+    { register: token, isSigner: false, isWritable: false },
+    { register: delegation, isSigner: true, isWritable: true },
+    { register: source, isSigner: false, isWritable: false },
+    { register: destination, isSigner: false, isWritable: false },
+  ], [...allPairs([0, 1, 2, 3]), [1, 4], [2, 4], [3, 4]], programId, payer, connection);
+
+  // TransferFrom: delegate, token, delegation, source, destination (source and destination may coincide)
+  await expectAliasFailure('TransferFrom', 5, amount(1n), [
+    { register: owner, isSigner: true, isWritable: false },
+    { register: token, isSigner: false, isWritable: false },
+    { register: delegation, isSigner: false, isWritable: true },
+    { register: source, isSigner: false, isWritable: true },
+    { register: destination, isSigner: false, isWritable: true },
+  ], [...allPairs([0, 1, 2, 3]), [0, 4], [1, 4], [2, 4]], programId, payer, connection);
+
+  // IncreaseApproval, DecreaseApproval, Revoke: owner, token, delegation
+  for (const [name, code, args] of [
+    ['IncreaseApproval', 6, amount(1n)],
+    ['DecreaseApproval', 7, amount(1n)],
+    ['Revoke', 8, []],
+  ]) {
+    await expectAliasFailure(name, code, args, [
+      { register: owner, isSigner: true, isWritable: false },
+      { register: token, isSigner: false, isWritable: false },
+      { register: delegation, isSigner: false, isWritable: true },
+    ], allPairs([0, 1, 2]), programId, payer, connection);
+  }
+
+  // AccountDestruction: owner, token, target
+  await expectAliasFailure('AccountDestruction', 9, [], [
+    { register: owner, isSigner: true, isWritable: false },
+    { register: token, isSigner: false, isWritable: false },
+    { register: destination, isSigner: false, isWritable: true },
+  ], allPairs([0, 1, 2]), programId, payer, connection);
+}
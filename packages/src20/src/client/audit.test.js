@@ -0,0 +1,47 @@
+const { sendAndConfirmTransaction, TransactionInstruction, Transaction } = require('@solana/web3.js');
+const soproxABI = require('soprox-abi');
+const { init, info } = require('./helpers');
+
+/**
+ * Audit supply
+ */
+const auditSupply = async (token, holders, programId, payer, connection) => {
+  console.log('Audit supply of', token.publicKey.toBase58());
+  const schema = [
+    { key: 'code', type: 'u8' },
+  ];
+  const layout = new soproxABI.struct(schema, {
+    code: 12,
+  });
+  const instruction = new TransactionInstruction({
+    keys: [
+      { pubkey: token.publicKey, isSigner: false, isWritable: false },
+      ...holders.map(holder => ({ pubkey: holder.publicKey, isSigner: false, isWritable: false })),
+    ],
+    programId,
+    data: layout.toBuffer()
+  });
+  const transaction = new Transaction();
+  transaction.add(instruction);
+  await sendAndConfirmTransaction(
+    connection, transaction, [payer],
+    {
+      skipPreflight: true,
+      commitment: 'recent',
+    });
+}
+
+module.exports = async function () {
+  console.log('\n\n*** Test audit\n');
+  const { connection, payer, programId, registers: [token, source, destination, delegation] } = await init();
+
+  console.log('Token info:', await info(token, connection));
+  await auditSupply(token, [source, destination], programId, payer, connection);
+  console.log('Supply matched');
+  try {
+    await auditSupply(token, [source], programId, payer, connection);
+  } catch (er) {
+    return console.log('Partial supply rejected');
+  }
+  throw new Error('Partial supply must be rejected');
+}
\ No newline at end of file
@@ -1,12 +1,24 @@
 const testConstructor = require('./constructor.test');
 const testTransfer = require('./transfer.test');
 const testApprove = require('./approve.test');
+const testAlias = require('./alias.test');
+const testNative = require('./native.test');
+const testAudit = require('./audit.test');
+const testMemo = require('./memo.test');
+const testInterest = require('./interest.test');
+const testSupply = require('./supply.test');
 const testDestruct = require('./destruct.test');
 
 const main = async () => {
   await testConstructor();
   await testTransfer();
   await testApprove();
+  await testAlias();
+  await testNative();
+  await testAudit();
+  await testMemo();
+  await testInterest();
+  await testSupply();
   await testDestruct();
 }
 
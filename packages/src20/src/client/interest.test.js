@@ -0,0 +1,121 @@
+const {
+  sendAndConfirmTransaction, TransactionInstruction, Transaction,
+  SYSVAR_CLOCK_PUBKEY,
+} = require('@solana/web3.js');
+const soproxABI = require('soprox-abi');
+const { init, info } = require('./helpers');
+
+/**
+ * Initialize interest rate
+ */
+const initializeInterestRate = async (rate, token, programId, payer, connection) => {
+  console.log('Initialize interest rate', rate, 'bps to', token.publicKey.toBase58());
+  const schema = [
+    { key: 'code', type: 'u8' },
+    { key: 'rate', type: 'i16' }
+  ];
+  const layout = new soproxABI.struct(schema, {
+    code: 15,
+    rate,
+  });
+  const instruction = new TransactionInstruction({
+    keys: [
+      { pubkey: payer.publicKey, isSigner: true, isWritable: false },
+      { pubkey: token.publicKey, isSigner: false, isWritable: true },
+      { pubkey: payer.publicKey, isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: layout.toBuffer()
+  });
+  const transaction = new Transaction();
+  transaction.add(instruction);
+  await sendAndConfirmTransaction(
+    connection, transaction, [payer],
+    {
+      skipPreflight: true,
+      commitment: 'recent',
+    });
+}
+
+/**
+ * Update interest rate
+ */
+const updateInterestRate = async (rate, token, programId, payer, connection) => {
+  console.log('Update interest rate', rate, 'bps to', token.publicKey.toBase58());
+  const schema = [
+    { key: 'code', type: 'u8' },
+    { key: 'rate', type: 'i16' }
+  ];
+  const layout = new soproxABI.struct(schema, {
+    code: 16,
+    rate,
+  });
+  const instruction = new TransactionInstruction({
+    keys: [
+      { pubkey: payer.publicKey, isSigner: true, isWritable: false },
+      { pubkey: token.publicKey, isSigner: false, isWritable: true },
+      { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: layout.toBuffer()
+  });
+  const transaction = new Transaction();
+  transaction.add(instruction);
+  await sendAndConfirmTransaction(
+    connection, transaction, [payer],
+    {
+      skipPreflight: true,
+      commitment: 'recent',
+    });
+}
+
+/**
+ * Amount to UI amount
+ */
+const amountToUiAmount = async (amount, token, programId, payer, connection) => {
+  console.log('Amount to UI amount', amount);
+  const schema = [
+    { key: 'code', type: 'u8' },
+    { key: 'amount', type: 'u64' }
+  ];
+  const layout = new soproxABI.struct(schema, {
+    code: 17,
+    amount,
+  });
+  const instruction = new TransactionInstruction({
+    keys: [
+      { pubkey: token.publicKey, isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: layout.toBuffer()
+  });
+  const transaction = new Transaction();
+  transaction.add(instruction);
+  const txId = await sendAndConfirmTransaction(
+    connection, transaction, [payer],
+    {
+      skipPreflight: true,
+      commitment: 'recent',
+    });
+  const { meta: { logMessages } } = await connection.getConfirmedTransaction(txId);
+  console.log(logMessages.find(log => log.includes('AmountToUiAmount:')));
+}
+
+module.exports = async function () {
+  console.log('\n\n*** Test interest\n');
+  const { connection, payer, programId, registers: [token, source, destination, delegation] } = await init();
+
+  try {
+    await initializeInterestRate(500, token, programId, payer, connection);
+  } catch (er) {
+    // Interest rate is already initialized
+    console.log('The interest rate may be initialized already');
+  }
+  console.log('Token info:', await info(token, connection));
+  await amountToUiAmount(1000000000n, token, programId, payer, connection);
+  await updateInterestRate(300, token, programId, payer, connection);
+  console.log('Token info:', await info(token, connection));
+  await amountToUiAmount(1000000000n, token, programId, payer, connection);
+}
\ No newline at end of file
@@ -0,0 +1,168 @@
+const { sendAndConfirmTransaction, TransactionInstruction, Transaction } = require('@solana/web3.js');
+const soproxABI = require('soprox-abi');
+const { init, info } = require('./helpers');
+
+/**
+ * Transfer with memo
+ */
+const transferWithMemo = async (amount, memo, token, source, destination, programId, payer, connection) => {
+  console.log('Transfer', amount, 'TOKEN to', destination.publicKey.toBase58(), 'with memo', memo);
+  const schema = [
+    { key: 'code', type: 'u8' },
+    { key: 'amount', type: 'u64' }
+  ];
+  const layout = new soproxABI.struct(schema, {
+    code: 13,
+    amount,
+  });
+  const memoBuffer = Buffer.from(memo, 'utf8');
+  const instruction = new TransactionInstruction({
+    keys: [
+      { pubkey: payer.publicKey, isSigner: true, isWritable: false },
+      { pubkey: token.publicKey, isSigner: false, isWritable: false },
+      { pubkey: source.publicKey, isSigner: false, isWritable: true },
+      { pubkey: destination.publicKey, isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.concat([layout.toBuffer(), Buffer.from([memoBuffer.length]), memoBuffer])
+  });
+  const transaction = new Transaction();
+  transaction.add(instruction);
+  await sendAndConfirmTransaction(
+    connection, transaction, [payer],
+    {
+      skipPreflight: true,
+      commitment: 'recent',
+    });
+}
+
+/**
+ * Transfer from with memo
+ */
+const transferFromWithMemo = async (amount, memo, token, delegation, source, destination, programId, payer, connection) => {
+  console.log('TransferFrom', amount, 'TOKEN to', destination.publicKey.toBase58(), 'with memo', memo);
+  const schema = [
+    { key: 'code', type: 'u8' },
+    { key: 'amount', type: 'u64' }
+  ];
+  const layout = new soproxABI.struct(schema, {
+    code: 18,
+    amount,
+  });
+  const memoBuffer = Buffer.from(memo, 'utf8');
+  const instruction = new TransactionInstruction({
+    keys: [
+      { pubkey: payer.publicKey, isSigner: true, isWritable: false },
+      { pubkey: token.publicKey, isSigner: false, isWritable: false },
+      { pubkey: delegation.publicKey, isSigner: false, isWritable: true },
+      { pubkey: source.publicKey, isSigner: false, isWritable: true },
+      { pubkey: destination.publicKey, isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: Buffer.concat([layout.toBuffer(), Buffer.from([memoBuffer.length]), memoBuffer])
+  });
+  const transaction = new Transaction();
+  transaction.add(instruction);
+  await sendAndConfirmTransaction(
+    connection, transaction, [payer],
+    {
+      skipPreflight: true,
+      commitment: 'recent',
+    });
+}
+
+/**
+ * Transfer
+ */
+const transfer = async (amount, token, source, destination, programId, payer, connection) => {
+  console.log('Transfer', amount, 'TOKEN to', destination.publicKey.toBase58());
+  const schema = [
+    { key: 'code', type: 'u8' },
+    { key: 'amount', type: 'u64' }
+  ];
+  const layout = new soproxABI.struct(schema, {
+    code: 3,
+    amount,
+  });
+  const instruction = new TransactionInstruction({
+    keys: [
+      { pubkey: payer.publicKey, isSigner: true, isWritable: false },
+      { pubkey: token.publicKey, isSigner: false, isWritable: false },
+      { pubkey: source.publicKey, isSigner: false, isWritable: true },
+      { pubkey: destination.publicKey, isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: layout.toBuffer()
+  });
+  const transaction = new Transaction();
+  transaction.add(instruction);
+  await sendAndConfirmTransaction(
+    connection, transaction, [payer],
+    {
+      skipPreflight: true,
+      commitment: 'recent',
+    });
+}
+
+/**
+ * Set require memo
+ */
+const setRequireMemo = async (requireMemo, token, target, programId, payer, connection) => {
+  console.log('Set require memo', requireMemo, 'to', target.publicKey.toBase58());
+  const schema = [
+    { key: 'code', type: 'u8' },
+    { key: 'requireMemo', type: 'bool' }
+  ];
+  const layout = new soproxABI.struct(schema, {
+    code: 14,
+    requireMemo,
+  });
+  const instruction = new TransactionInstruction({
+    keys: [
+      { pubkey: payer.publicKey, isSigner: true, isWritable: false },
+      { pubkey: token.publicKey, isSigner: false, isWritable: false },
+      { pubkey: target.publicKey, isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: layout.toBuffer()
+  });
+  const transaction = new Transaction();
+  transaction.add(instruction);
+  await sendAndConfirmTransaction(
+    connection, transaction, [payer],
+    {
+      skipPreflight: true,
+      commitment: 'recent',
+    });
+}
+
+module.exports = async function () {
+  console.log('\n\n*** Test memo\n');
+  const { connection, payer, programId, registers: [token, source, destination, delegation] } = await init();
+
+  await transferWithMemo(1000n, 'deposit #1', token, source, destination, programId, payer, connection);
+  console.log('New destination data:', await info(destination, connection));
+
+  await setRequireMemo(true, token, destination, programId, payer, connection);
+  console.log('Destination data:', await info(destination, connection));
+  try {
+    await transfer(1000n, token, source, destination, programId, payer, connection);
+    throw new Error('Plain transfer must be rejected');
+  } catch (er) {
+    if (er.message == 'Plain transfer must be rejected') throw er;
+    console.log('Plain transfer rejected');
+  }
+  await transferWithMemo(1000n, 'deposit #2', token, source, destination, programId, payer, connection);
+  console.log('New destination data:', await info(destination, connection));
+  try {
+    await transferWithMemo(1000n, '', token, source, destination, programId, payer, connection);
+    throw new Error('Empty memo must be rejected');
+  } catch (er) {
+    if (er.message == 'Empty memo must be rejected') throw er;
+    console.log('Empty memo rejected');
+  }
+  // The delegation from the approve test still has allowance left
+  await transferFromWithMemo(1000n, 'deposit #3', token, delegation, source, destination, programId, payer, connection);
+  console.log('New destination data:', await info(destination, connection));
+  await setRequireMemo(false, token, destination, programId, payer, connection);
+}
\ No newline at end of file
@@ -0,0 +1,135 @@
+const {
+  sendAndConfirmTransaction, TransactionInstruction, Transaction, Account,
+  SystemProgram, SYSVAR_RENT_PUBKEY,
+} = require('@solana/web3.js');
+const soproxABI = require('soprox-abi');
+const { deployRegister } = require('../../lib/network');
+const { init, info } = require('./helpers');
+
+/**
+ * Native token constructor
+ */
+const nativeTokenConstructor = async (token, programId, payer, connection) => {
+  console.log('Native token contructor at', token.publicKey.toBase58());
+  const schema = [
+    { key: 'code', type: 'u8' },
+  ];
+  const layout = new soproxABI.struct(schema, {
+    code: 10,
+  });
+  const instruction = new TransactionInstruction({
+    keys: [
+      { pubkey: payer.publicKey, isSigner: true, isWritable: false },
+      { pubkey: token.publicKey, isSigner: true, isWritable: true },
+    ],
+    programId,
+    data: layout.toBuffer()
+  });
+  const transaction = new Transaction();
+  transaction.add(instruction);
+  await sendAndConfirmTransaction(
+    connection,
+    transaction,
+    [
+      payer,
+      new Account(Buffer.from(token.secretKey, 'hex'))
+    ],
+    {
+      skipPreflight: true,
+      commitment: 'recent',
+    });
+}
+
+/**
+ * Account constructor
+ */
+const accountConstructor = async (token, account, programId, payer, connection) => {
+  console.log('Account constructor at', account.publicKey.toBase58());
+  const schema = [
+    { key: 'code', type: 'u8' },
+  ];
+  const layout = new soproxABI.struct(schema, {
+    code: 1,
+  });
+  const instruction = new TransactionInstruction({
+    keys: [
+      { pubkey: payer.publicKey, isSigner: true, isWritable: false },
+      { pubkey: token.publicKey, isSigner: false, isWritable: false },
+      { pubkey: account.publicKey, isSigner: true, isWritable: true },
+    ],
+    programId,
+    data: layout.toBuffer()
+  });
+  const transaction = new Transaction();
+  transaction.add(instruction);
+  await sendAndConfirmTransaction(
+    connection,
+    transaction,
+    [
+      payer,
+      new Account(Buffer.from(account.secretKey, 'hex'))
+    ],
+    {
+      skipPreflight: true,
+      commitment: 'recent',
+    });
+}
+
+/**
+ * Deposit lamports and sync native
+ */
+const wrap = async (lamports, token, account, programId, payer, connection) => {
+  console.log('Wrap', lamports, 'lamports into', account.publicKey.toBase58());
+  const schema = [
+    { key: 'code', type: 'u8' },
+  ];
+  const layout = new soproxABI.struct(schema, {
+    code: 11,
+  });
+  const instruction = new TransactionInstruction({
+    keys: [
+      { pubkey: token.publicKey, isSigner: false, isWritable: true },
+      { pubkey: account.publicKey, isSigner: false, isWritable: true },
+      { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: layout.toBuffer()
+  });
+  const transaction = new Transaction();
+  transaction.add(SystemProgram.transfer({
+    fromPubkey: payer.publicKey,
+    toPubkey: account.publicKey,
+    lamports,
+  }));
+  transaction.add(instruction);
+  await sendAndConfirmTransaction(
+    connection, transaction, [payer],
+    {
+      skipPreflight: true,
+      commitment: 'recent',
+    });
+}
+
+module.exports = async function () {
+  console.log('\n\n*** Test native\n');
+  const { connection, payer, programId, registers: [token, source, destination, delegation] } = await init();
+
+  // The native token and account reuse the token and account layouts
+  const register = async ({ schema }) => {
+    const account = await deployRegister(soproxABI.span({ schema }), payer, programId, connection);
+    return {
+      schema,
+      publicKey: account.publicKey,
+      secretKey: Buffer.from(account.secretKey).toString('hex'),
+    };
+  }
+  const nativeToken = await register(token);
+  const nativeAccount = await register(source);
+  await nativeTokenConstructor(nativeToken, programId, payer, connection);
+  await accountConstructor(nativeToken, nativeAccount, programId, payer, connection);
+  console.log('Current native token data:', await info(nativeToken, connection));
+  console.log('Current native account data:', await info(nativeAccount, connection));
+  await wrap(1000000, nativeToken, nativeAccount, programId, payer, connection);
+  console.log('New native token data:', await info(nativeToken, connection));
+  console.log('New native account data:', await info(nativeAccount, connection));
+}
@@ -0,0 +1,49 @@
+const { sendAndConfirmTransaction, TransactionInstruction, Transaction } = require('@solana/web3.js');
+const soproxABI = require('soprox-abi');
+const { init, info } = require('./helpers');
+
+/**
+ * Mint to or burn from an account, both signed by the payer
+ * as token authority and account owner
+ */
+const changeSupply = async (code, amount, token, account, programId, payer, connection) => {
+  console.log(code == 19 ? 'Mint' : 'Burn', amount, code == 19 ? 'to' : 'from', account.publicKey.toBase58());
+  const schema = [
+    { key: 'code', type: 'u8' },
+    { key: 'amount', type: 'u64' }
+  ];
+  const layout = new soproxABI.struct(schema, {
+    code,
+    amount,
+  });
+  const instruction = new TransactionInstruction({
+    keys: [
+      { pubkey: payer.publicKey, isSigner: true, isWritable: false },
+      { pubkey: token.publicKey, isSigner: false, isWritable: true },
+      { pubkey: account.publicKey, isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: layout.toBuffer()
+  });
+  const transaction = new Transaction();
+  transaction.add(instruction);
+  await sendAndConfirmTransaction(
+    connection, transaction, [payer],
+    {
+      skipPreflight: true,
+      commitment: 'recent',
+    });
+}
+
+module.exports = async function () {
+  console.log('\n\n*** Test supply\n');
+  const { connection, payer, programId, registers: [token, source, destination, delegation] } = await init();
+
+  console.log('Token info:', await info(token, connection));
+  await changeSupply(19, 1000n, token, source, programId, payer, connection);
+  console.log('Token info:', await info(token, connection));
+  console.log('Source info:', await info(source, connection));
+  await changeSupply(20, 1000n, token, source, programId, payer, connection);
+  console.log('Token info:', await info(token, connection));
+  console.log('Source info:', await info(source, connection));
+}
\ No newline at end of file
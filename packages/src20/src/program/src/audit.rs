@@ -0,0 +1,158 @@
+use crate::error::AppError;
+use crate::schema::{account::Account, token::Token};
+use solana_program::{
+  program_error::ProgramError,
+  program_pack::{IsInitialized, Pack},
+  pubkey::Pubkey,
+};
+
+/// An account as seen by the audit: key, owner and data
+pub type Snapshot<'a> = (&'a Pubkey, &'a Pubkey, &'a [u8]);
+
+//
+// Define the audit result
+//
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SupplyAudit {
+  pub total_supply: u64,
+  pub balances: u64,
+}
+
+impl SupplyAudit {
+  /// Sum the balances of the token accounts, used by AuditSupply on-chain
+  /// and by clients on fetched accounts before sending it
+  pub fn from_snapshot(
+    program_id: &Pubkey,
+    token: Snapshot,
+    accounts: &[Snapshot],
+  ) -> Result<Self, ProgramError> {
+    let (token_key, token_owner, token_data) = token;
+    if token_owner != program_id {
+      return Err(AppError::IncorrectProgramId.into());
+    }
+    let token_data = Token::unpack(token_data)?;
+    if !token_data.is_initialized() {
+      return Err(AppError::NotInitialized.into());
+    }
+    let mut balances: u64 = 0;
+    for (i, &(key, owner, data)) in accounts.iter().enumerate() {
+      // Each account must be counted once
+      if key == token_key || accounts[i + 1..].iter().any(|other| other.0 == key) {
+        return Err(AppError::DuplicateAccount.into());
+      }
+      if owner != program_id {
+        return Err(AppError::IncorrectProgramId.into());
+      }
+      let account_data = Account::unpack(data)?;
+      if account_data.token != *token_key {
+        return Err(AppError::IncorrectTokenId.into());
+      }
+      balances = balances
+        .checked_add(account_data.amount)
+        .ok_or(AppError::Overflow)?;
+    }
+    Ok(SupplyAudit {
+      total_supply: token_data.total_supply,
+      balances,
+    })
+  }
+
+  pub fn is_matched(&self) -> bool {
+    self.total_supply == self.balances
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn pack_token(total_supply: u64) -> Vec<u8> {
+    let token = Token {
+      total_supply,
+      initialized: true,
+      ..Token::default()
+    };
+    let mut data = vec![0; Token::LEN];
+    Token::pack(token, &mut data).unwrap();
+    data
+  }
+
+  fn pack_account(token: &Pubkey, amount: u64) -> Vec<u8> {
+    let account = Account {
+      token: *token,
+      amount,
+      initialized: true,
+      ..Account::default()
+    };
+    let mut data = vec![0; Account::LEN];
+    Account::pack(account, &mut data).unwrap();
+    data
+  }
+
+  #[test]
+  fn sums_the_balances() {
+    let program_id = Pubkey::new_unique();
+    let (token, src, dst) = (
+      Pubkey::new_unique(),
+      Pubkey::new_unique(),
+      Pubkey::new_unique(),
+    );
+    let token_data = pack_token(100);
+    let (src_data, dst_data) = (pack_account(&token, 60), pack_account(&token, 40));
+    let accounts = [
+      (&src, &program_id, &src_data[..]),
+      (&dst, &program_id, &dst_data[..]),
+    ];
+    let audit =
+      SupplyAudit::from_snapshot(&program_id, (&token, &program_id, &token_data), &accounts)
+        .unwrap();
+    assert_eq!(
+      audit,
+      SupplyAudit {
+        total_supply: 100,
+        balances: 100
+      }
+    );
+    assert!(audit.is_matched());
+    let audit = SupplyAudit::from_snapshot(
+      &program_id,
+      (&token, &program_id, &token_data),
+      &accounts[..1],
+    )
+    .unwrap();
+    assert!(!audit.is_matched());
+  }
+
+  #[test]
+  fn counts_each_account_once() {
+    let program_id = Pubkey::new_unique();
+    let (token, src) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let token_data = pack_token(100);
+    let src_data = pack_account(&token, 50);
+    let accounts = [
+      (&src, &program_id, &src_data[..]),
+      (&src, &program_id, &src_data[..]),
+    ];
+    assert_eq!(
+      SupplyAudit::from_snapshot(&program_id, (&token, &program_id, &token_data), &accounts),
+      Err(AppError::DuplicateAccount.into())
+    );
+  }
+
+  #[test]
+  fn rejects_accounts_of_other_tokens() {
+    let program_id = Pubkey::new_unique();
+    let (token, other, src) = (
+      Pubkey::new_unique(),
+      Pubkey::new_unique(),
+      Pubkey::new_unique(),
+    );
+    let token_data = pack_token(100);
+    let src_data = pack_account(&other, 100);
+    let accounts = [(&src, &program_id, &src_data[..])];
+    assert_eq!(
+      SupplyAudit::from_snapshot(&program_id, (&token, &program_id, &token_data), &accounts),
+      Err(AppError::IncorrectTokenId.into())
+    );
+  }
+}
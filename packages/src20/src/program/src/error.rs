@@ -27,6 +27,16 @@ pub enum AppError {
   NotInitialized,
   #[error("Operation overflowed")]
   Overflow,
+  #[error("Duplicate account")]
+  DuplicateAccount,
+  #[error("Not a native token")]
+  NotNative,
+  #[error("Supply unmatched")]
+  UnmatchedSupply,
+  #[error("Invalid memo")]
+  InvalidMemo,
+  #[error("Memo required")]
+  MemoRequired,
 }
 
 impl From<AppError> for ProgramError {
@@ -54,6 +64,11 @@ impl PrintProgramError for AppError {
       AppError::ConstructorOnce => info!("Error: Already constructed"),
       AppError::NotInitialized => info!("Error: Not yet initialized"),
       AppError::Overflow => info!("Error: Operation overflowed"),
+      AppError::DuplicateAccount => info!("Error: Duplicate account"),
+      AppError::NotNative => info!("Error: Not a native token"),
+      AppError::UnmatchedSupply => info!("Error: Supply unmatched"),
+      AppError::InvalidMemo => info!("Error: Invalid memo"),
+      AppError::MemoRequired => info!("Error: Memo required"),
     }
   }
 }
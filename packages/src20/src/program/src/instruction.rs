@@ -1,6 +1,9 @@
 use crate::error::AppError;
 use solana_program::program_error::ProgramError;
-use std::{char, convert::TryInto};
+use std::{char, convert::TryInto, str};
+
+/// Maximum length of a memo in bytes
+pub const MAX_MEMO_LEN: usize = 128;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum AppInstruction {
@@ -30,6 +33,35 @@ pub enum AppInstruction {
   },
   Revoke {},
   AccountDestruction {},
+  NativeTokenConstructor {},
+  SyncNative {},
+  AuditSupply {},
+  TransferWithMemo {
+    amount: u64,
+    memo: String,
+  },
+  SetRequireMemo {
+    require_memo: bool,
+  },
+  InitializeInterestRate {
+    rate: i16,
+  },
+  UpdateInterestRate {
+    rate: i16,
+  },
+  AmountToUiAmount {
+    amount: u64,
+  },
+  TransferFromWithMemo {
+    amount: u64,
+    memo: String,
+  },
+  MintTo {
+    amount: u64,
+  },
+  Burn {
+    amount: u64,
+  },
 }
 
 impl AppInstruction {
@@ -95,6 +127,81 @@ impl AppInstruction {
       8 => Self::Revoke {},
       // Destruct
       9 => Self::AccountDestruction {},
+      // Native token constructor
+      10 => Self::NativeTokenConstructor {},
+      // Sync native
+      11 => Self::SyncNative {},
+      // Audit supply
+      12 => Self::AuditSupply {},
+      // TransferWithMemo, TransferFromWithMemo
+      13 | 18 => {
+        let amount = rest
+          .get(..8)
+          .and_then(|slice| slice.try_into().ok())
+          .map(u64::from_le_bytes)
+          .ok_or(AppError::InvalidInstruction)?;
+        let len = rest
+          .get(8)
+          .map(|&len| len as usize)
+          .ok_or(AppError::InvalidInstruction)?;
+        if len == 0 || len > MAX_MEMO_LEN {
+          return Err(AppError::InvalidMemo.into());
+        }
+        let memo = rest
+          .get(9..9 + len)
+          .and_then(|slice| str::from_utf8(slice).ok())
+          .ok_or(AppError::InvalidMemo)?
+          .to_string();
+        match tag {
+          13 => Self::TransferWithMemo { amount, memo },
+          18 => Self::TransferFromWithMemo { amount, memo },
+          _ => unreachable!(),
+        }
+      }
+      // Set require memo
+      14 => {
+        let require_memo = match rest.get(0) {
+          Some(0) => false,
+          Some(1) => true,
+          _ => return Err(AppError::InvalidInstruction.into()),
+        };
+        Self::SetRequireMemo { require_memo }
+      }
+      // InitializeInterestRate, UpdateInterestRate
+      15 | 16 => {
+        let rate = rest
+          .get(..2)
+          .and_then(|slice| slice.try_into().ok())
+          .map(i16::from_le_bytes)
+          .ok_or(AppError::InvalidInstruction)?;
+        match tag {
+          15 => Self::InitializeInterestRate { rate },
+          16 => Self::UpdateInterestRate { rate },
+          _ => unreachable!(),
+        }
+      }
+      // Amount to UI amount
+      17 => {
+        let amount = rest
+          .get(..8)
+          .and_then(|slice| slice.try_into().ok())
+          .map(u64::from_le_bytes)
+          .ok_or(AppError::InvalidInstruction)?;
+        Self::AmountToUiAmount { amount }
+      }
+      // MintTo, Burn
+      19 | 20 => {
+        let amount = rest
+          .get(..8)
+          .and_then(|slice| slice.try_into().ok())
+          .map(u64::from_le_bytes)
+          .ok_or(AppError::InvalidInstruction)?;
+        match tag {
+          19 => Self::MintTo { amount },
+          20 => Self::Burn { amount },
+          _ => unreachable!(),
+        }
+      }
       _ => return Err(AppError::InvalidInstruction.into()),
     })
   }
@@ -1,3 +1,4 @@
+use crate::{error::AppError, instruction::MAX_MEMO_LEN};
 use arrayref::mut_array_refs;
 use solana_program::{
   instruction::{AccountMeta, Instruction},
@@ -258,6 +259,132 @@ impl ISRC20 {
     // Build accounts
     let mut accounts = Vec::with_capacity(3);
     accounts.push(AccountMeta::new_readonly(owner, true));
+    // Writable for native tokens whose supply shrinks
+    accounts.push(AccountMeta::new(token_acc, false));
+    accounts.push(AccountMeta::new(target_acc, false));
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+
+  pub fn native_token_constructor(
+    program_id: Pubkey,
+    deployer: Pubkey,
+    token_acc: Pubkey,
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // NativeTokenConstructor - Code 10
+    data.push(10);
+    // Build accounts
+    let mut accounts = Vec::with_capacity(2);
+    accounts.push(AccountMeta::new_readonly(deployer, true));
+    accounts.push(AccountMeta::new(token_acc, true));
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+
+  pub fn sync_native(
+    program_id: Pubkey,
+    token_acc: Pubkey,
+    target_acc: Pubkey,
+    sysvar_rent_acc: Pubkey,
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // SyncNative - Code 11
+    data.push(11);
+    // Build accounts
+    let mut accounts = Vec::with_capacity(3);
+    accounts.push(AccountMeta::new(token_acc, false));
+    accounts.push(AccountMeta::new(target_acc, false));
+    accounts.push(AccountMeta::new_readonly(sysvar_rent_acc, false));
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+
+  pub fn audit_supply(
+    program_id: Pubkey,
+    token_acc: Pubkey,
+    holder_accs: &[Pubkey],
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // AuditSupply - Code 12
+    data.push(12);
+    // Build accounts
+    let mut accounts = Vec::with_capacity(1 + holder_accs.len());
+    accounts.push(AccountMeta::new_readonly(token_acc, false));
+    for holder_acc in holder_accs {
+      accounts.push(AccountMeta::new_readonly(*holder_acc, false));
+    }
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+
+  pub fn transfer_with_memo(
+    program_id: Pubkey,
+    owner: Pubkey,
+    token_acc: Pubkey,
+    src_acc: Pubkey,
+    dst_acc: Pubkey,
+    amount: u64,
+    memo: &str,
+  ) -> Result<Instruction, ProgramError> {
+    if memo.is_empty() || memo.len() > MAX_MEMO_LEN {
+      return Err(AppError::InvalidMemo.into());
+    }
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // TransferWithMemo - Code 13
+    data.push(13);
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.push(memo.len() as u8);
+    data.extend_from_slice(memo.as_bytes());
+    // Build accounts
+    let mut accounts = Vec::with_capacity(4);
+    accounts.push(AccountMeta::new_readonly(owner, true));
+    accounts.push(AccountMeta::new_readonly(token_acc, false));
+    accounts.push(AccountMeta::new(src_acc, false));
+    accounts.push(AccountMeta::new(dst_acc, false));
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+
+  pub fn set_require_memo(
+    program_id: Pubkey,
+    owner: Pubkey,
+    token_acc: Pubkey,
+    target_acc: Pubkey,
+    require_memo: bool,
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // SetRequireMemo - Code 14
+    data.push(14);
+    data.push(require_memo as u8);
+    // Build accounts
+    let mut accounts = Vec::with_capacity(3);
+    accounts.push(AccountMeta::new_readonly(owner, true));
     accounts.push(AccountMeta::new_readonly(token_acc, false));
     accounts.push(AccountMeta::new(target_acc, false));
     // Return
@@ -267,4 +394,166 @@ impl ISRC20 {
       data,
     })
   }
+
+  pub fn initialize_interest_rate(
+    program_id: Pubkey,
+    authority: Pubkey,
+    token_acc: Pubkey,
+    rate_authority: Pubkey,
+    sysvar_clock_acc: Pubkey,
+    rate: i16,
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // InitializeInterestRate - Code 15
+    data.push(15);
+    data.extend_from_slice(&rate.to_le_bytes());
+    // Build accounts
+    let mut accounts = Vec::with_capacity(4);
+    accounts.push(AccountMeta::new_readonly(authority, true));
+    accounts.push(AccountMeta::new(token_acc, false));
+    accounts.push(AccountMeta::new_readonly(rate_authority, false));
+    accounts.push(AccountMeta::new_readonly(sysvar_clock_acc, false));
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+
+  pub fn update_interest_rate(
+    program_id: Pubkey,
+    rate_authority: Pubkey,
+    token_acc: Pubkey,
+    sysvar_clock_acc: Pubkey,
+    rate: i16,
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // UpdateInterestRate - Code 16
+    data.push(16);
+    data.extend_from_slice(&rate.to_le_bytes());
+    // Build accounts
+    let mut accounts = Vec::with_capacity(3);
+    accounts.push(AccountMeta::new_readonly(rate_authority, true));
+    accounts.push(AccountMeta::new(token_acc, false));
+    accounts.push(AccountMeta::new_readonly(sysvar_clock_acc, false));
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+
+  pub fn amount_to_ui_amount(
+    program_id: Pubkey,
+    token_acc: Pubkey,
+    sysvar_clock_acc: Pubkey,
+    amount: u64,
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // AmountToUiAmount - Code 17
+    data.push(17);
+    data.extend_from_slice(&amount.to_le_bytes());
+    // Build accounts
+    let mut accounts = Vec::with_capacity(2);
+    accounts.push(AccountMeta::new_readonly(token_acc, false));
+    accounts.push(AccountMeta::new_readonly(sysvar_clock_acc, false));
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+
+  // The accounts of transfer_from plus the memo, a struct would only hide them
+  #[allow(clippy::too_many_arguments)]
+  pub fn transfer_from_with_memo(
+    program_id: Pubkey,
+    dlg_acc: Pubkey,
+    token_acc: Pubkey,
+    delegation_acc: Pubkey,
+    src_acc: Pubkey,
+    dst_acc: Pubkey,
+    amount: u64,
+    memo: &str,
+  ) -> Result<Instruction, ProgramError> {
+    if memo.is_empty() || memo.len() > MAX_MEMO_LEN {
+      return Err(AppError::InvalidMemo.into());
+    }
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // TransferFromWithMemo - Code 18
+    data.push(18);
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.push(memo.len() as u8);
+    data.extend_from_slice(memo.as_bytes());
+    // Build accounts
+    let mut accounts = Vec::with_capacity(5);
+    accounts.push(AccountMeta::new_readonly(dlg_acc, true));
+    accounts.push(AccountMeta::new_readonly(token_acc, false));
+    accounts.push(AccountMeta::new(delegation_acc, false));
+    accounts.push(AccountMeta::new(src_acc, false));
+    accounts.push(AccountMeta::new(dst_acc, false));
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+
+  pub fn mint_to(
+    program_id: Pubkey,
+    authority: Pubkey,
+    token_acc: Pubkey,
+    dst_acc: Pubkey,
+    amount: u64,
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // MintTo - Code 19
+    data.push(19);
+    data.extend_from_slice(&amount.to_le_bytes());
+    // Build accounts
+    let mut accounts = Vec::with_capacity(3);
+    accounts.push(AccountMeta::new_readonly(authority, true));
+    accounts.push(AccountMeta::new(token_acc, false));
+    accounts.push(AccountMeta::new(dst_acc, false));
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+
+  pub fn burn(
+    program_id: Pubkey,
+    owner: Pubkey,
+    token_acc: Pubkey,
+    src_acc: Pubkey,
+    amount: u64,
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // Burn - Code 20
+    data.push(20);
+    data.extend_from_slice(&amount.to_le_bytes());
+    // Build accounts
+    let mut accounts = Vec::with_capacity(3);
+    accounts.push(AccountMeta::new_readonly(owner, true));
+    accounts.push(AccountMeta::new(token_acc, false));
+    accounts.push(AccountMeta::new(src_acc, false));
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
 }
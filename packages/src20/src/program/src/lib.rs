@@ -1,3 +1,4 @@
+pub mod audit;
 pub mod entrypoint;
 pub mod error;
 pub mod instruction;
@@ -1,3 +1,4 @@
+use crate::audit::{Snapshot, SupplyAudit};
 use crate::error::AppError;
 use crate::instruction::AppInstruction;
 use crate::schema::{account::Account, delegation::Delegation, token::Token};
@@ -7,6 +8,8 @@ use solana_program::{
   info,
   program_pack::{IsInitialized, Pack},
   pubkey::Pubkey,
+  rent::Rent,
+  sysvar::{clock::Clock, Sysvar},
 };
 
 pub struct Processor {}
@@ -33,6 +36,7 @@ impl Processor {
         let deployer = next_account_info(accounts_iter)?;
         let token_acc = next_account_info(accounts_iter)?;
         let dst_acc = next_account_info(accounts_iter)?;
+        Self::assert_unique(&[deployer, token_acc, dst_acc])?;
         if token_acc.owner != program_id || dst_acc.owner != program_id {
           return Err(AppError::IncorrectProgramId.into());
         }
@@ -50,6 +54,10 @@ impl Processor {
         token_data.total_supply = total_supply;
         token_data.decimals = decimals;
         token_data.initialized = true;
+        // Legacy-sized tokens cannot hold extensions, they are left without authority
+        if token_acc.data_len() == Token::LEN {
+          token_data.authority = *deployer.key;
+        }
         Token::pack(token_data, &mut token_acc.data.borrow_mut())?;
         // Account
         dst_data.owner = *deployer.key;
@@ -73,6 +81,7 @@ impl Processor {
         let caller = next_account_info(accounts_iter)?;
         let token_acc = next_account_info(accounts_iter)?;
         let target_acc = next_account_info(accounts_iter)?;
+        Self::assert_unique(&[caller, token_acc, target_acc])?;
         if token_acc.owner != program_id || target_acc.owner != program_id {
           return Err(AppError::IncorrectProgramId.into());
         }
@@ -109,48 +118,7 @@ impl Processor {
       //
       AppInstruction::Transfer { amount } => {
         info!("Calling Transfer function");
-        // Extract accounts: owner, token, source, destination
-        let accounts_iter = &mut accounts.iter();
-        let owner = next_account_info(accounts_iter)?;
-        let token_acc = next_account_info(accounts_iter)?;
-        let src_acc = next_account_info(accounts_iter)?;
-        let dst_acc = next_account_info(accounts_iter)?;
-        if token_acc.owner != program_id
-          || src_acc.owner != program_id
-          || dst_acc.owner != program_id
-        {
-          return Err(AppError::IncorrectProgramId.into());
-        }
-        // Extract accounts data
-        let token_data = Token::unpack(&token_acc.data.borrow())?;
-        let mut src_data = Account::unpack(&src_acc.data.borrow())?;
-        let mut dst_data = Account::unpack(&dst_acc.data.borrow())?;
-        if !token_data.is_initialized() || !src_data.is_initialized() || !dst_data.is_initialized()
-        {
-          return Err(AppError::NotInitialized.into());
-        }
-        if src_data.token != *token_acc.key || dst_data.token != *token_acc.key {
-          return Err(AppError::IncorrectTokenId.into());
-        }
-        if !owner.is_signer || *owner.key != src_data.owner {
-          return Err(AppError::InvalidOwner.into());
-        }
-        if *src_acc.key == *dst_acc.key {
-          return Ok(());
-        }
-        // From
-        src_data.amount = src_data
-          .amount
-          .checked_sub(amount)
-          .ok_or(AppError::Overflow)?;
-        Account::pack(src_data, &mut src_acc.data.borrow_mut())?;
-        // To
-        dst_data.amount = dst_data
-          .amount
-          .checked_add(amount)
-          .ok_or(AppError::Overflow)?;
-        Account::pack(dst_data, &mut dst_acc.data.borrow_mut())?;
-        Ok(())
+        Self::transfer(program_id, accounts, amount, None)
       }
 
       //
@@ -165,6 +133,9 @@ impl Processor {
         let delegation_acc = next_account_info(accounts_iter)?;
         let src_acc = next_account_info(accounts_iter)?;
         let dlg_acc = next_account_info(accounts_iter)?;
+        // The owner may delegate to itself
+        Self::assert_unique(&[owner, token_acc, delegation_acc, src_acc])?;
+        Self::assert_unique(&[dlg_acc, token_acc, delegation_acc, src_acc])?;
         if token_acc.owner != program_id
           || delegation_acc.owner != program_id
           || src_acc.owner != program_id
@@ -202,64 +173,7 @@ impl Processor {
       //
       AppInstruction::TransferFrom { amount } => {
         info!("Calling TransferFrom function");
-        // Extract accounts: delegated, token, delegation, source, destination
-        let accounts_iter = &mut accounts.iter();
-        let dlg_acc = next_account_info(accounts_iter)?;
-        let token_acc = next_account_info(accounts_iter)?;
-        let delegation_acc = next_account_info(accounts_iter)?;
-        let src_acc = next_account_info(accounts_iter)?;
-        let dst_acc = next_account_info(accounts_iter)?;
-        if token_acc.owner != program_id
-          || delegation_acc.owner != program_id
-          || src_acc.owner != program_id
-          || dst_acc.owner != program_id
-        {
-          return Err(AppError::IncorrectProgramId.into());
-        }
-        // Extract accounts data
-        let token_data = Token::unpack(&token_acc.data.borrow())?;
-        let mut delegation_data = Delegation::unpack_unchecked(&delegation_acc.data.borrow())?;
-        let mut src_data = Account::unpack(&src_acc.data.borrow())?;
-        let mut dst_data = Account::unpack(&dst_acc.data.borrow())?;
-        if !token_data.is_initialized()
-          || !delegation_data.is_initialized()
-          || !src_data.is_initialized()
-          || !dst_data.is_initialized()
-        {
-          return Err(AppError::NotInitialized.into());
-        }
-        if delegation_data.token != *token_acc.key
-          || src_data.token != *token_acc.key
-          || dst_data.token != *token_acc.key
-        {
-          return Err(AppError::IncorrectTokenId.into());
-        }
-        if !dlg_acc.is_signer || *dlg_acc.key != delegation_data.delegate {
-          return Err(AppError::InvalidOwner.into());
-        }
-        if *src_acc.key == *dst_acc.key {
-          return Ok(());
-        }
-        // Delegation
-        delegation_data.amount = delegation_data
-          .amount
-          .checked_sub(amount)
-          .ok_or(AppError::Overflow)?;
-        Delegation::pack(delegation_data, &mut delegation_acc.data.borrow_mut())?;
-        // From
-        src_data.amount = src_data
-          .amount
-          .checked_sub(amount)
-          .ok_or(AppError::Overflow)?;
-        Account::pack(src_data, &mut src_acc.data.borrow_mut())?;
-        // To
-        dst_data.amount = dst_data
-          .amount
-          .checked_add(amount)
-          .ok_or(AppError::Overflow)?;
-        Account::pack(dst_data, &mut dst_acc.data.borrow_mut())?;
-
-        Ok(())
+        Self::transfer_from(program_id, accounts, amount, None)
       }
 
       //
@@ -272,6 +186,7 @@ impl Processor {
         let owner = next_account_info(accounts_iter)?;
         let token_acc = next_account_info(accounts_iter)?;
         let delegation_acc = next_account_info(accounts_iter)?;
+        Self::assert_unique(&[owner, token_acc, delegation_acc])?;
         if token_acc.owner != program_id || delegation_acc.owner != program_id {
           return Err(AppError::IncorrectProgramId.into());
         }
@@ -307,6 +222,7 @@ impl Processor {
         let owner = next_account_info(accounts_iter)?;
         let token_acc = next_account_info(accounts_iter)?;
         let delegation_acc = next_account_info(accounts_iter)?;
+        Self::assert_unique(&[owner, token_acc, delegation_acc])?;
         if token_acc.owner != program_id || delegation_acc.owner != program_id {
           return Err(AppError::IncorrectProgramId.into());
         }
@@ -341,6 +257,7 @@ impl Processor {
         let owner = next_account_info(accounts_iter)?;
         let token_acc = next_account_info(accounts_iter)?;
         let delegation_acc = next_account_info(accounts_iter)?;
+        Self::assert_unique(&[owner, token_acc, delegation_acc])?;
         if token_acc.owner != program_id || delegation_acc.owner != program_id {
           return Err(AppError::IncorrectProgramId.into());
         }
@@ -376,18 +293,30 @@ impl Processor {
         let owner = next_account_info(accounts_iter)?;
         let token_acc = next_account_info(accounts_iter)?;
         let target_acc = next_account_info(accounts_iter)?;
+        Self::assert_unique(&[owner, token_acc, target_acc])?;
         if token_acc.owner != program_id || target_acc.owner != program_id {
           return Err(AppError::IncorrectProgramId.into());
         }
         // Extract and change account data
-        let token_data = Token::unpack(&token_acc.data.borrow())?;
+        let mut token_data = Token::unpack(&token_acc.data.borrow())?;
         let mut target_data = Account::unpack_unchecked(&target_acc.data.borrow())?;
         if !token_data.is_initialized() || !target_data.is_initialized() {
           return Err(AppError::NotInitialized.into());
         }
+        if target_data.token != *token_acc.key {
+          return Err(AppError::IncorrectTokenId.into());
+        }
         if !owner.is_signer || *owner.key != target_data.owner {
           return Err(AppError::InvalidOwner.into());
         }
+        // Unwrap native balances, the lamports go to the owner below
+        if token_data.native {
+          token_data.total_supply = token_data
+            .total_supply
+            .checked_sub(target_data.amount)
+            .ok_or(AppError::Overflow)?;
+          Token::pack(token_data, &mut token_acc.data.borrow_mut())?;
+        }
 
         let balance = owner.lamports();
         **owner.lamports.borrow_mut() = balance
@@ -399,6 +328,526 @@ impl Processor {
 
         Ok(())
       }
+
+      //
+      // Native token constructor, code 10
+      //
+      AppInstruction::NativeTokenConstructor {} => {
+        info!("Calling NativeTokenConstructor function");
+        // Extract accounts: deployer, token
+        let accounts_iter = &mut accounts.iter();
+        let deployer = next_account_info(accounts_iter)?;
+        let token_acc = next_account_info(accounts_iter)?;
+        Self::assert_unique(&[deployer, token_acc])?;
+        if token_acc.owner != program_id {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+        if !deployer.is_signer || !token_acc.is_signer {
+          return Err(AppError::InvalidOwner.into());
+        }
+        // Write constructor data
+        let mut token_data = Token::unpack_unchecked(&token_acc.data.borrow())?;
+        if token_data.is_initialized() {
+          return Err(AppError::ConstructorOnce.into());
+        }
+        // The supply grows as lamports are synced into accounts
+        token_data.symbol = ['S', 'O', 'L', '-'];
+        token_data.total_supply = 0;
+        token_data.decimals = 9;
+        token_data.native = true;
+        token_data.initialized = true;
+        token_data.authority = *deployer.key;
+        Token::pack(token_data, &mut token_acc.data.borrow_mut())?;
+        Ok(())
+      }
+
+      //
+      // Sync native, code 11
+      //
+      AppInstruction::SyncNative {} => {
+        info!("Calling SyncNative function");
+        // Extract accounts: token, target, rent sysvar
+        let accounts_iter = &mut accounts.iter();
+        let token_acc = next_account_info(accounts_iter)?;
+        let target_acc = next_account_info(accounts_iter)?;
+        let sysvar_rent_acc = next_account_info(accounts_iter)?;
+        Self::assert_unique(&[token_acc, target_acc])?;
+        if token_acc.owner != program_id || target_acc.owner != program_id {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+        // Extract accounts data
+        let mut token_data = Token::unpack(&token_acc.data.borrow())?;
+        let mut target_data = Account::unpack(&target_acc.data.borrow())?;
+        if !token_data.is_initialized() || !target_data.is_initialized() {
+          return Err(AppError::NotInitialized.into());
+        }
+        if !token_data.native {
+          return Err(AppError::NotNative.into());
+        }
+        if target_data.token != *token_acc.key {
+          return Err(AppError::IncorrectTokenId.into());
+        }
+        // Lamports above the rent-exempt minimum are the balance
+        let rent = Rent::from_account_info(sysvar_rent_acc)?;
+        let amount = target_acc
+          .lamports()
+          .checked_sub(rent.minimum_balance(target_acc.data_len()))
+          .ok_or(AppError::Overflow)?;
+        let deposit = amount
+          .checked_sub(target_data.amount)
+          .ok_or(AppError::Overflow)?;
+        // Token
+        token_data.total_supply = token_data
+          .total_supply
+          .checked_add(deposit)
+          .ok_or(AppError::Overflow)?;
+        Token::pack(token_data, &mut token_acc.data.borrow_mut())?;
+        // Account
+        target_data.amount = amount;
+        Account::pack(target_data, &mut target_acc.data.borrow_mut())?;
+        Ok(())
+      }
+
+      //
+      // Audit the supply of a token, code 12
+      //
+      AppInstruction::AuditSupply {} => {
+        info!("Calling AuditSupply function");
+        // Extract accounts: token, followed by all of its accounts
+        let accounts_iter = &mut accounts.iter();
+        let token_acc = next_account_info(accounts_iter)?;
+        let holder_accs: Vec<_> = accounts_iter.collect();
+        let token_data = token_acc.data.borrow();
+        let holder_datas: Vec<_> = holder_accs.iter().map(|acc| acc.data.borrow()).collect();
+        let holders: Vec<Snapshot> = holder_accs
+          .iter()
+          .zip(holder_datas.iter())
+          .map(|(acc, data)| (acc.key, acc.owner, &data[..]))
+          .collect();
+        let audit = SupplyAudit::from_snapshot(
+          program_id,
+          (token_acc.key, token_acc.owner, &token_data[..]),
+          &holders,
+        )?;
+        info!(&format!(
+          "AuditSupply: total supply {}, balances {}, matched {}",
+          audit.total_supply,
+          audit.balances,
+          audit.is_matched()
+        ));
+        if !audit.is_matched() {
+          return Err(AppError::UnmatchedSupply.into());
+        }
+        Ok(())
+      }
+
+      //
+      // Transfer token with a memo, code 13
+      //
+      AppInstruction::TransferWithMemo { amount, memo } => {
+        info!("Calling TransferWithMemo function");
+        Self::transfer(program_id, accounts, amount, Some(&memo))
+      }
+
+      //
+      // Require memos on incoming transfers, code 14
+      //
+      AppInstruction::SetRequireMemo { require_memo } => {
+        info!("Calling SetRequireMemo function");
+        // Extract accounts: owner, token, target
+        let accounts_iter = &mut accounts.iter();
+        let owner = next_account_info(accounts_iter)?;
+        let token_acc = next_account_info(accounts_iter)?;
+        let target_acc = next_account_info(accounts_iter)?;
+        Self::assert_unique(&[owner, token_acc, target_acc])?;
+        if token_acc.owner != program_id || target_acc.owner != program_id {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+        // Extract accounts data
+        let mut target_data = Account::unpack(&target_acc.data.borrow())?;
+        if !target_data.is_initialized() {
+          return Err(AppError::NotInitialized.into());
+        }
+        if target_data.token != *token_acc.key {
+          return Err(AppError::IncorrectTokenId.into());
+        }
+        if !owner.is_signer || *owner.key != target_data.owner {
+          return Err(AppError::InvalidOwner.into());
+        }
+        target_data.require_memo = require_memo;
+        Account::pack(target_data, &mut target_acc.data.borrow_mut())?;
+        Ok(())
+      }
+
+      //
+      // Make a token interest-bearing, code 15
+      //
+      AppInstruction::InitializeInterestRate { rate } => {
+        info!("Calling InitializeInterestRate function");
+        // Extract accounts: authority, token, rate authority, clock sysvar
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let token_acc = next_account_info(accounts_iter)?;
+        let rate_authority = next_account_info(accounts_iter)?;
+        let sysvar_clock_acc = next_account_info(accounts_iter)?;
+        Self::assert_unique(&[authority, token_acc])?;
+        Self::assert_unique(&[token_acc, rate_authority])?;
+        if token_acc.owner != program_id {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+        // Extract accounts data
+        let mut token_data = Token::unpack(&token_acc.data.borrow())?;
+        if !token_data.is_initialized() {
+          return Err(AppError::NotInitialized.into());
+        }
+        // Legacy tokens have no authority, nobody can configure them
+        if !authority.is_signer
+          || token_data.authority == Pubkey::default()
+          || *authority.key != token_data.authority
+          || *rate_authority.key == Pubkey::default()
+        {
+          return Err(AppError::InvalidOwner.into());
+        }
+        if token_data.is_interest_bearing() {
+          return Err(AppError::ConstructorOnce.into());
+        }
+        let clock = Clock::from_account_info(sysvar_clock_acc)?;
+        token_data.rate_authority = *rate_authority.key;
+        token_data.initialization_timestamp = clock.unix_timestamp;
+        token_data.pre_update_average_rate = rate;
+        token_data.last_update_timestamp = clock.unix_timestamp;
+        token_data.rate = rate;
+        Token::pack(token_data, &mut token_acc.data.borrow_mut())?;
+        Ok(())
+      }
+
+      //
+      // Change the interest rate, code 16
+      //
+      AppInstruction::UpdateInterestRate { rate } => {
+        info!("Calling UpdateInterestRate function");
+        // Extract accounts: rate authority, token, clock sysvar
+        let accounts_iter = &mut accounts.iter();
+        let rate_authority = next_account_info(accounts_iter)?;
+        let token_acc = next_account_info(accounts_iter)?;
+        let sysvar_clock_acc = next_account_info(accounts_iter)?;
+        Self::assert_unique(&[rate_authority, token_acc])?;
+        if token_acc.owner != program_id {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+        // Extract accounts data
+        let mut token_data = Token::unpack(&token_acc.data.borrow())?;
+        if !token_data.is_initialized() || !token_data.is_interest_bearing() {
+          return Err(AppError::NotInitialized.into());
+        }
+        if !rate_authority.is_signer || *rate_authority.key != token_data.rate_authority {
+          return Err(AppError::InvalidOwner.into());
+        }
+        // Fold the accrued interest into the average before switching rates
+        let clock = Clock::from_account_info(sysvar_clock_acc)?;
+        token_data.pre_update_average_rate = token_data.average_rate(clock.unix_timestamp);
+        token_data.last_update_timestamp = clock.unix_timestamp;
+        token_data.rate = rate;
+        Token::pack(token_data, &mut token_acc.data.borrow_mut())?;
+        Ok(())
+      }
+
+      //
+      // Log the display amount with accrued interest, code 17
+      //
+      AppInstruction::AmountToUiAmount { amount } => {
+        info!("Calling AmountToUiAmount function");
+        // Extract accounts: token, clock sysvar
+        let accounts_iter = &mut accounts.iter();
+        let token_acc = next_account_info(accounts_iter)?;
+        let sysvar_clock_acc = next_account_info(accounts_iter)?;
+        if token_acc.owner != program_id {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+        // Extract accounts data
+        let token_data = Token::unpack(&token_acc.data.borrow())?;
+        if !token_data.is_initialized() {
+          return Err(AppError::NotInitialized.into());
+        }
+        let clock = Clock::from_account_info(sysvar_clock_acc)?;
+        info!(&format!(
+          "AmountToUiAmount: {} is {}",
+          amount,
+          token_data
+            .ui_amount(amount, clock.unix_timestamp)
+            .ok_or(AppError::Overflow)?
+        ));
+        Ok(())
+      }
+
+      //
+      // Transfer on behalf of the source owner with a memo, code 18
+      //
+      AppInstruction::TransferFromWithMemo { amount, memo } => {
+        info!("Calling TransferFromWithMemo function");
+        Self::transfer_from(program_id, accounts, amount, Some(&memo))
+      }
+
+      //
+      // Issue new tokens, code 19
+      //
+      AppInstruction::MintTo { amount } => {
+        info!("Calling MintTo function");
+        // Extract accounts: authority, token, destination
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let token_acc = next_account_info(accounts_iter)?;
+        let dst_acc = next_account_info(accounts_iter)?;
+        Self::assert_unique(&[authority, token_acc, dst_acc])?;
+        if token_acc.owner != program_id || dst_acc.owner != program_id {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+        // Extract accounts data
+        let mut token_data = Token::unpack(&token_acc.data.borrow())?;
+        let mut dst_data = Account::unpack(&dst_acc.data.borrow())?;
+        if !token_data.is_initialized() || !dst_data.is_initialized() {
+          return Err(AppError::NotInitialized.into());
+        }
+        if dst_data.token != *token_acc.key {
+          return Err(AppError::IncorrectTokenId.into());
+        }
+        // Legacy tokens have no authority, their supply is fixed
+        if !authority.is_signer
+          || token_data.authority == Pubkey::default()
+          || *authority.key != token_data.authority
+        {
+          return Err(AppError::InvalidOwner.into());
+        }
+        // Native supply follows the lamports
+        if token_data.native {
+          return Err(AppError::InvalidInstruction.into());
+        }
+        token_data.total_supply = token_data
+          .total_supply
+          .checked_add(amount)
+          .ok_or(AppError::Overflow)?;
+        dst_data.amount = dst_data
+          .amount
+          .checked_add(amount)
+          .ok_or(AppError::Overflow)?;
+        Token::pack(token_data, &mut token_acc.data.borrow_mut())?;
+        Account::pack(dst_data, &mut dst_acc.data.borrow_mut())?;
+        Ok(())
+      }
+
+      //
+      // Destroy tokens, code 20
+      //
+      AppInstruction::Burn { amount } => {
+        info!("Calling Burn function");
+        // Extract accounts: owner, token, source
+        let accounts_iter = &mut accounts.iter();
+        let owner = next_account_info(accounts_iter)?;
+        let token_acc = next_account_info(accounts_iter)?;
+        let src_acc = next_account_info(accounts_iter)?;
+        Self::assert_unique(&[owner, token_acc, src_acc])?;
+        if token_acc.owner != program_id || src_acc.owner != program_id {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+        // Extract accounts data
+        let mut token_data = Token::unpack(&token_acc.data.borrow())?;
+        let mut src_data = Account::unpack(&src_acc.data.borrow())?;
+        if !token_data.is_initialized() || !src_data.is_initialized() {
+          return Err(AppError::NotInitialized.into());
+        }
+        if src_data.token != *token_acc.key {
+          return Err(AppError::IncorrectTokenId.into());
+        }
+        if !owner.is_signer || *owner.key != src_data.owner {
+          return Err(AppError::InvalidOwner.into());
+        }
+        // Native supply follows the lamports, unwrap by destroying the account instead
+        if token_data.native {
+          return Err(AppError::InvalidInstruction.into());
+        }
+        src_data.amount = src_data
+          .amount
+          .checked_sub(amount)
+          .ok_or(AppError::Overflow)?;
+        token_data.total_supply = token_data
+          .total_supply
+          .checked_sub(amount)
+          .ok_or(AppError::Overflow)?;
+        Account::pack(src_data, &mut src_acc.data.borrow_mut())?;
+        Token::pack(token_data, &mut token_acc.data.borrow_mut())?;
+        Ok(())
+      }
+    }
+  }
+
+  /// Transfer between two accounts, plain transfers are refused by accounts requiring memos
+  pub fn transfer(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    memo: Option<&str>,
+  ) -> ProgramResult {
+    // Extract accounts: owner, token, source, destination
+    let accounts_iter = &mut accounts.iter();
+    let owner = next_account_info(accounts_iter)?;
+    let token_acc = next_account_info(accounts_iter)?;
+    let src_acc = next_account_info(accounts_iter)?;
+    let dst_acc = next_account_info(accounts_iter)?;
+    // Source and destination may coincide, it's a no-op transfer
+    Self::assert_unique(&[owner, token_acc, src_acc])?;
+    Self::assert_unique(&[owner, token_acc, dst_acc])?;
+    if token_acc.owner != program_id || src_acc.owner != program_id || dst_acc.owner != program_id {
+      return Err(AppError::IncorrectProgramId.into());
+    }
+    // Extract accounts data
+    let token_data = Token::unpack(&token_acc.data.borrow())?;
+    let mut src_data = Account::unpack(&src_acc.data.borrow())?;
+    let mut dst_data = Account::unpack(&dst_acc.data.borrow())?;
+    if !token_data.is_initialized() || !src_data.is_initialized() || !dst_data.is_initialized() {
+      return Err(AppError::NotInitialized.into());
+    }
+    if src_data.token != *token_acc.key || dst_data.token != *token_acc.key {
+      return Err(AppError::IncorrectTokenId.into());
+    }
+    if !owner.is_signer || *owner.key != src_data.owner {
+      return Err(AppError::InvalidOwner.into());
+    }
+    if memo.is_none() && dst_data.require_memo {
+      return Err(AppError::MemoRequired.into());
+    }
+    if *src_acc.key == *dst_acc.key {
+      return Ok(());
+    }
+    // From
+    src_data.amount = src_data
+      .amount
+      .checked_sub(amount)
+      .ok_or(AppError::Overflow)?;
+    Account::pack(src_data, &mut src_acc.data.borrow_mut())?;
+    // To
+    dst_data.amount = dst_data
+      .amount
+      .checked_add(amount)
+      .ok_or(AppError::Overflow)?;
+    Account::pack(dst_data, &mut dst_acc.data.borrow_mut())?;
+    // Native balances are backed by lamports
+    if token_data.native {
+      Self::transfer_lamports(src_acc, dst_acc, amount)?;
+    }
+    if let Some(memo) = memo {
+      info!(&format!(
+        "Transfer {} from {} to {} with memo: {}",
+        amount, src_acc.key, dst_acc.key, memo
+      ));
+    }
+    Ok(())
+  }
+
+  /// Transfer on behalf of the owner, plain transfers are refused by accounts requiring memos
+  pub fn transfer_from(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+    memo: Option<&str>,
+  ) -> ProgramResult {
+    // Extract accounts: delegated, token, delegation, source, destination
+    let accounts_iter = &mut accounts.iter();
+    let dlg_acc = next_account_info(accounts_iter)?;
+    let token_acc = next_account_info(accounts_iter)?;
+    let delegation_acc = next_account_info(accounts_iter)?;
+    let src_acc = next_account_info(accounts_iter)?;
+    let dst_acc = next_account_info(accounts_iter)?;
+    // Source and destination may coincide, it's a no-op transfer
+    Self::assert_unique(&[dlg_acc, token_acc, delegation_acc, src_acc])?;
+    Self::assert_unique(&[dlg_acc, token_acc, delegation_acc, dst_acc])?;
+    if token_acc.owner != program_id
+      || delegation_acc.owner != program_id
+      || src_acc.owner != program_id
+      || dst_acc.owner != program_id
+    {
+      return Err(AppError::IncorrectProgramId.into());
+    }
+    // Extract accounts data
+    let token_data = Token::unpack(&token_acc.data.borrow())?;
+    let mut delegation_data = Delegation::unpack_unchecked(&delegation_acc.data.borrow())?;
+    let mut src_data = Account::unpack(&src_acc.data.borrow())?;
+    let mut dst_data = Account::unpack(&dst_acc.data.borrow())?;
+    if !token_data.is_initialized()
+      || !delegation_data.is_initialized()
+      || !src_data.is_initialized()
+      || !dst_data.is_initialized()
+    {
+      return Err(AppError::NotInitialized.into());
+    }
+    if delegation_data.token != *token_acc.key
+      || src_data.token != *token_acc.key
+      || dst_data.token != *token_acc.key
+    {
+      return Err(AppError::IncorrectTokenId.into());
+    }
+    if !dlg_acc.is_signer || *dlg_acc.key != delegation_data.delegate {
+      return Err(AppError::InvalidOwner.into());
+    }
+    if memo.is_none() && dst_data.require_memo {
+      return Err(AppError::MemoRequired.into());
+    }
+    if *src_acc.key == *dst_acc.key {
+      return Ok(());
+    }
+    // Delegation
+    delegation_data.amount = delegation_data
+      .amount
+      .checked_sub(amount)
+      .ok_or(AppError::Overflow)?;
+    Delegation::pack(delegation_data, &mut delegation_acc.data.borrow_mut())?;
+    // From
+    src_data.amount = src_data
+      .amount
+      .checked_sub(amount)
+      .ok_or(AppError::Overflow)?;
+    Account::pack(src_data, &mut src_acc.data.borrow_mut())?;
+    // To
+    dst_data.amount = dst_data
+      .amount
+      .checked_add(amount)
+      .ok_or(AppError::Overflow)?;
+    Account::pack(dst_data, &mut dst_acc.data.borrow_mut())?;
+    // Native balances are backed by lamports
+    if token_data.native {
+      Self::transfer_lamports(src_acc, dst_acc, amount)?;
+    }
+    if let Some(memo) = memo {
+      info!(&format!(
+        "TransferFrom {} from {} to {} with memo: {}",
+        amount, src_acc.key, dst_acc.key, memo
+      ));
+    }
+    Ok(())
+  }
+
+  /// Move lamports between two accounts owned by the program
+  pub fn transfer_lamports(
+    src_acc: &AccountInfo,
+    dst_acc: &AccountInfo,
+    amount: u64,
+  ) -> ProgramResult {
+    let src_balance = src_acc.lamports();
+    let dst_balance = dst_acc.lamports();
+    **src_acc.lamports.borrow_mut() = src_balance.checked_sub(amount).ok_or(AppError::Overflow)?;
+    **dst_acc.lamports.borrow_mut() = dst_balance.checked_add(amount).ok_or(AppError::Overflow)?;
+    Ok(())
+  }
+
+  /// Reject the same account playing several roles in one instruction
+  pub fn assert_unique(accounts: &[&AccountInfo]) -> ProgramResult {
+    for (i, account) in accounts.iter().enumerate() {
+      if accounts[i + 1..]
+        .iter()
+        .any(|other| other.key == account.key)
+      {
+        return Err(AppError::DuplicateAccount.into());
+      }
     }
+    Ok(())
   }
 }
@@ -14,6 +14,18 @@ pub struct Account {
   pub token: Pubkey,
   pub amount: u64,
   pub initialized: bool,
+  // Appended after the original layout, absent from legacy accounts
+  pub require_memo: bool,
+}
+
+impl Account {
+  /// Length of the accounts created before the extensions were appended
+  pub const LEGACY_LEN: usize = 32 + 32 + 8 + 1;
+
+  /// Whether the data only holds the original fields, so it still fits a legacy account
+  pub fn fits_legacy(&self) -> bool {
+    !self.require_memo
+  }
 }
 
 //
@@ -34,13 +46,28 @@ impl IsInitialized for Account {
 // Implement Pack trait
 //
 impl Pack for Account {
-  // Fixed length
-  const LEN: usize = 32 + 32 + 8 + 1;
+  // Fixed length of the current accounts
+  const LEN: usize = Self::LEGACY_LEN + 1;
+  // Legacy accounts are shorter, their extensions read as default
+  fn unpack_unchecked(input: &[u8]) -> Result<Self, ProgramError> {
+    if input.len() != Self::LEN && input.len() != Self::LEGACY_LEN {
+      return Err(ProgramError::InvalidAccountData);
+    }
+    Self::unpack_from_slice(input)
+  }
+  // Legacy accounts cannot store extensions, they are never dropped silently
+  fn pack(src: Self, dst: &mut [u8]) -> Result<(), ProgramError> {
+    if dst.len() != Self::LEN && !(dst.len() == Self::LEGACY_LEN && src.fits_legacy()) {
+      return Err(ProgramError::InvalidAccountData);
+    }
+    src.pack_into_slice(dst);
+    Ok(())
+  }
   // Unpack data from [u8] to the data struct
   fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-    let src = array_ref![src, 0, 73];
-    let (owner, token, amount, initialized) = array_refs![src, 32, 32, 8, 1];
-    Ok(Account {
+    let legacy = array_ref![src, 0, 73];
+    let (owner, token, amount, initialized) = array_refs![legacy, 32, 32, 8, 1];
+    let mut account = Account {
       owner: Pubkey::new_from_array(*owner),
       token: Pubkey::new_from_array(*token),
       amount: u64::from_le_bytes(*amount),
@@ -49,21 +76,76 @@ impl Pack for Account {
         [1] => true,
         _ => return Err(ProgramError::InvalidAccountData),
       },
-    })
+      ..Account::default()
+    };
+    if src.len() == Self::LEGACY_LEN {
+      return Ok(account);
+    }
+    let require_memo = array_ref![src, 73, 1];
+    account.require_memo = match require_memo {
+      [0] => false,
+      [1] => true,
+      _ => return Err(ProgramError::InvalidAccountData),
+    };
+    Ok(account)
   }
   // Pack data from the data struct to [u8]
   fn pack_into_slice(&self, dst: &mut [u8]) {
-    let dst = array_mut_ref![dst, 0, 73];
-    let (dst_owner, dst_token, dst_amount, dst_initialized) = mut_array_refs![dst, 32, 32, 8, 1];
+    let len = dst.len();
+    let legacy = array_mut_ref![dst, 0, 73];
+    let (dst_owner, dst_token, dst_amount, dst_initialized) = mut_array_refs![legacy, 32, 32, 8, 1];
     let &Account {
       ref owner,
       ref token,
       amount,
       initialized,
+      require_memo,
     } = self;
     dst_owner.copy_from_slice(owner.as_ref());
     dst_token.copy_from_slice(token.as_ref());
     *dst_amount = amount.to_le_bytes();
     *dst_initialized = [initialized as u8];
+    if len == Self::LEGACY_LEN {
+      return;
+    }
+    let dst_require_memo = array_mut_ref![dst, 73, 1];
+    *dst_require_memo = [require_memo as u8];
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn legacy_accounts_keep_their_layout() {
+    let account = Account {
+      owner: Pubkey::new_unique(),
+      token: Pubkey::new_unique(),
+      amount: 1_000,
+      initialized: true,
+      ..Account::default()
+    };
+    let mut legacy = [0u8; Account::LEGACY_LEN];
+    Account::pack(account, &mut legacy).unwrap();
+    let mut current = [0u8; Account::LEN];
+    Account::pack(account, &mut current).unwrap();
+    assert_eq!(legacy[..], current[..Account::LEGACY_LEN]);
+    assert_eq!(Account::unpack(&legacy).unwrap(), account);
+    assert_eq!(Account::unpack(&current).unwrap(), account);
+  }
+
+  #[test]
+  fn legacy_accounts_cannot_require_memos() {
+    let account = Account {
+      initialized: true,
+      require_memo: true,
+      ..Account::default()
+    };
+    let mut legacy = [0u8; Account::LEGACY_LEN];
+    assert_eq!(
+      Account::pack(account, &mut legacy),
+      Err(ProgramError::InvalidAccountData)
+    );
   }
 }
@@ -2,8 +2,15 @@ use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
 use solana_program::{
   program_error::ProgramError,
   program_pack::{IsInitialized, Pack, Sealed},
+  pubkey::Pubkey,
 };
-use std::{char, convert::TryInto};
+use std::{
+  char,
+  convert::{TryFrom, TryInto},
+};
+
+/// Seconds in an average year of 365.24 days, rates are yearly
+pub const SECONDS_PER_YEAR: i64 = 60 * 60 * 24 * 36524 / 100;
 
 //
 // Define the data struct
@@ -14,6 +21,93 @@ pub struct Token {
   pub total_supply: u64,
   pub decimals: u8,
   pub initialized: bool,
+  // Appended after the original layout, absent from legacy accounts
+  pub native: bool,
+  // Interest-bearing configuration, unset while the rate authority is the default key
+  pub rate_authority: Pubkey,
+  pub initialization_timestamp: i64,
+  pub pre_update_average_rate: i16,
+  pub last_update_timestamp: i64,
+  pub rate: i16,
+  // Configures the token, unset on legacy tokens
+  pub authority: Pubkey,
+}
+
+impl Token {
+  /// Length of the accounts created before the extensions were appended
+  pub const LEGACY_LEN: usize = 4 * 4 + 8 + 1 + 1;
+
+  /// Whether the data only holds the original fields, so it still fits a legacy account
+  pub fn fits_legacy(&self) -> bool {
+    *self
+      == Token {
+        symbol: self.symbol,
+        total_supply: self.total_supply,
+        decimals: self.decimals,
+        initialized: self.initialized,
+        ..Token::default()
+      }
+  }
+
+  pub fn is_interest_bearing(&self) -> bool {
+    self.rate_authority != Pubkey::default()
+  }
+
+  /// Average rate in bps since initialization, to be stored before changing the rate
+  pub fn average_rate(&self, now: i64) -> i16 {
+    let total = now.saturating_sub(self.initialization_timestamp);
+    if total <= 0 {
+      return self.rate;
+    }
+    let pre_update = self
+      .last_update_timestamp
+      .saturating_sub(self.initialization_timestamp);
+    let post_update = now.saturating_sub(self.last_update_timestamp);
+    ((self.pre_update_average_rate as i128 * pre_update as i128
+      + self.rate as i128 * post_update as i128)
+      / total as i128) as i16
+  }
+
+  /// Raw amount with the interest continuously accrued until now, in integer maths.
+  /// Every term of the series is rounded toward zero, so it may be off by a few raw units.
+  pub fn accrued_amount(&self, raw_amount: u64, now: i64) -> Option<u64> {
+    if !self.is_interest_bearing() {
+      return Some(raw_amount);
+    }
+    let pre_update = self
+      .last_update_timestamp
+      .saturating_sub(self.initialization_timestamp);
+    let post_update = now.saturating_sub(self.last_update_timestamp);
+    // amount * e^x with x = numerator / denominator, summed as amount * x^k / k!
+    let numerator = (self.pre_update_average_rate as i128)
+      .checked_mul(pre_update as i128)?
+      .checked_add((self.rate as i128).checked_mul(post_update as i128)?)?;
+    let denominator = SECONDS_PER_YEAR as i128 * 10_000;
+    let mut term = raw_amount as i128;
+    let mut sum = term;
+    let mut k = 1;
+    while term != 0 {
+      term = term.checked_mul(numerator)? / (denominator * k);
+      sum = sum.checked_add(term)?;
+      k += 1;
+    }
+    u64::try_from(sum.max(0)).ok()
+  }
+
+  /// Display amount of a raw amount with the accrued interest, as a fixed-point decimal
+  pub fn ui_amount(&self, raw_amount: u64, now: i64) -> Option<String> {
+    let amount = self.accrued_amount(raw_amount, now)? as u128;
+    if self.decimals == 0 {
+      return Some(amount.to_string());
+    }
+    let unit = 10_u128.checked_pow(self.decimals as u32)?;
+    Some(format!(
+      "{}.{:0width$}",
+      amount / unit,
+      amount % unit,
+      width = self.decimals as usize
+    ))
+  }
 }
 
 //
@@ -34,19 +128,34 @@ impl IsInitialized for Token {
 // Implement Pack trait
 //
 impl Pack for Token {
-  // Fixed length
-  const LEN: usize = 4 * 4 + 8 + 1 + 1;
+  // Fixed length of the current accounts
+  const LEN: usize = Self::LEGACY_LEN + 1 + 32 + 8 + 2 + 8 + 2 + 32;
+  // Legacy accounts are shorter, their extensions read as default
+  fn unpack_unchecked(input: &[u8]) -> Result<Self, ProgramError> {
+    if input.len() != Self::LEN && input.len() != Self::LEGACY_LEN {
+      return Err(ProgramError::InvalidAccountData);
+    }
+    Self::unpack_from_slice(input)
+  }
+  // Legacy accounts cannot store extensions, they are never dropped silently
+  fn pack(src: Self, dst: &mut [u8]) -> Result<(), ProgramError> {
+    if dst.len() != Self::LEN && !(dst.len() == Self::LEGACY_LEN && src.fits_legacy()) {
+      return Err(ProgramError::InvalidAccountData);
+    }
+    src.pack_into_slice(dst);
+    Ok(())
+  }
   // Unpack data from [u8] to the data struct
   fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-    let src = array_ref![src, 0, 26];
-    let (symbol, total_supply, decimals, initialized) = array_refs![src, 16, 8, 1, 1];
+    let legacy = array_ref![src, 0, 26];
+    let (symbol, total_supply, decimals, initialized) = array_refs![legacy, 16, 8, 1, 1];
     let vec_symbol: Vec<_> = symbol
       .chunks(4)
       .map(|slice| slice.try_into().unwrap())
       .map(|slice| u32::from_le_bytes(slice))
       .map(|slice| char::from_u32(slice).unwrap())
       .collect();
-    Ok(Token {
+    let mut token = Token {
       symbol: [vec_symbol[0], vec_symbol[1], vec_symbol[2], vec_symbol[3]],
       total_supply: u64::from_le_bytes(*total_supply),
       decimals: u8::from_le_bytes(*decimals),
@@ -55,19 +164,53 @@ impl Pack for Token {
         [1] => true,
         _ => return Err(ProgramError::InvalidAccountData),
       },
-    })
+      ..Token::default()
+    };
+    if src.len() == Self::LEGACY_LEN {
+      return Ok(token);
+    }
+    let extensions = array_ref![src, 26, 85];
+    let (
+      native,
+      rate_authority,
+      initialization_timestamp,
+      pre_update_average_rate,
+      last_update_timestamp,
+      rate,
+      authority,
+    ) = array_refs![extensions, 1, 32, 8, 2, 8, 2, 32];
+    token.native = match native {
+      [0] => false,
+      [1] => true,
+      _ => return Err(ProgramError::InvalidAccountData),
+    };
+    token.rate_authority = Pubkey::new_from_array(*rate_authority);
+    token.initialization_timestamp = i64::from_le_bytes(*initialization_timestamp);
+    token.pre_update_average_rate = i16::from_le_bytes(*pre_update_average_rate);
+    token.last_update_timestamp = i64::from_le_bytes(*last_update_timestamp);
+    token.rate = i16::from_le_bytes(*rate);
+    token.authority = Pubkey::new_from_array(*authority);
+    Ok(token)
   }
   // Pack data from the data struct to [u8]
   fn pack_into_slice(&self, dst: &mut [u8]) {
-    let dst = array_mut_ref![dst, 0, 26];
+    let len = dst.len();
+    let legacy = array_mut_ref![dst, 0, 26];
     let (dst_symbol, dst_total_supply, dst_decimals, dst_initialized) =
-      mut_array_refs![dst, 16, 8, 1, 1];
+      mut_array_refs![legacy, 16, 8, 1, 1];
     let (first_sym, second_sym, third_sym, forth_sym) = mut_array_refs![dst_symbol, 4, 4, 4, 4];
     let &Token {
       symbol,
       total_supply,
       decimals,
       initialized,
+      native,
+      ref rate_authority,
+      initialization_timestamp,
+      pre_update_average_rate,
+      last_update_timestamp,
+      rate,
+      ref authority,
     } = self;
     symbol[0].encode_utf8(first_sym);
     symbol[1].encode_utf8(second_sym);
@@ -76,5 +219,109 @@ impl Pack for Token {
     *dst_total_supply = total_supply.to_le_bytes();
     *dst_decimals = decimals.to_le_bytes();
     *dst_initialized = [initialized as u8];
+    if len == Self::LEGACY_LEN {
+      return;
+    }
+    let extensions = array_mut_ref![dst, 26, 85];
+    let (
+      dst_native,
+      dst_rate_authority,
+      dst_initialization_timestamp,
+      dst_pre_update_average_rate,
+      dst_last_update_timestamp,
+      dst_rate,
+      dst_authority,
+    ) = mut_array_refs![extensions, 1, 32, 8, 2, 8, 2, 32];
+    *dst_native = [native as u8];
+    dst_rate_authority.copy_from_slice(rate_authority.as_ref());
+    *dst_initialization_timestamp = initialization_timestamp.to_le_bytes();
+    *dst_pre_update_average_rate = pre_update_average_rate.to_le_bytes();
+    *dst_last_update_timestamp = last_update_timestamp.to_le_bytes();
+    *dst_rate = rate.to_le_bytes();
+    dst_authority.copy_from_slice(authority.as_ref());
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn legacy_accounts_keep_their_layout() {
+    let token = Token {
+      symbol: ['S', 'N', 'T', 'K'],
+      total_supply: 1_000_000,
+      decimals: 9,
+      initialized: true,
+      ..Token::default()
+    };
+    let mut legacy = [0u8; Token::LEGACY_LEN];
+    Token::pack(token, &mut legacy).unwrap();
+    let mut current = [0u8; Token::LEN];
+    Token::pack(token, &mut current).unwrap();
+    assert_eq!(legacy[..], current[..Token::LEGACY_LEN]);
+    assert_eq!(Token::unpack(&legacy).unwrap(), token);
+    assert_eq!(Token::unpack(&current).unwrap(), token);
+  }
+
+  #[test]
+  fn legacy_accounts_reject_extensions() {
+    let token = Token {
+      initialized: true,
+      native: true,
+      ..Token::default()
+    };
+    let mut legacy = [0u8; Token::LEGACY_LEN];
+    assert_eq!(
+      Token::pack(token, &mut legacy),
+      Err(ProgramError::InvalidAccountData)
+    );
+  }
+
+  #[test]
+  fn accrues_interest_in_fixed_point() {
+    let token = Token {
+      decimals: 9,
+      initialized: true,
+      rate_authority: Pubkey::new_unique(),
+      rate: 500,
+      pre_update_average_rate: 500,
+      ..Token::default()
+    };
+    // e^0.05 = 1.051271096..., each term of the series rounds toward zero
+    assert_eq!(token.accrued_amount(1_000_000_000, 0), Some(1_000_000_000));
+    assert_eq!(
+      token.accrued_amount(1_000_000_000, SECONDS_PER_YEAR),
+      Some(1_051_271_095)
+    );
+    assert_eq!(
+      token.ui_amount(1_000_000_000, SECONDS_PER_YEAR),
+      Some("1.051271095".to_string())
+    );
+    // e^-0.05 = 0.951229424...
+    let token = Token {
+      rate: -500,
+      ..token
+    };
+    assert_eq!(
+      token.accrued_amount(1_000_000_000, SECONDS_PER_YEAR),
+      Some(951_229_425)
+    );
+  }
+
+  #[test]
+  fn displays_plain_amounts() {
+    let token = Token {
+      decimals: 2,
+      initialized: true,
+      ..Token::default()
+    };
+    assert_eq!(token.ui_amount(12_345, 100), Some("123.45".to_string()));
+    assert_eq!(token.ui_amount(5, 100), Some("0.05".to_string()));
+    let token = Token {
+      decimals: 0,
+      ..token
+    };
+    assert_eq!(token.ui_amount(5, 100), Some("5".to_string()));
   }
 }
@@ -7,20 +7,6 @@ const soproxABI = require('soprox-abi');
 const store = require('../lib/store');
 const soproxConf = require('../soprox.config.json');
 
-/**
- * Safely create account that falls off the curve
- */
-const safelyCreateAccount = async (programId) => {
-  const account = new Account();
-  const seeds = [account.publicKey.toBuffer()];
-  try {
-    await PublicKey.createProgramAddress(seeds, programId);
-    return account;
-  } catch (er) {
-    return await safelyCreateAccount(programId);
-  }
-};
-
 /**
  * Establish a connection to the cluster
  */
@@ -62,7 +48,7 @@ const deployProgram = async (data, payer, connection) => {
  * Deploy a register to the cluster
  */
 const deployRegister = async (space, payer, programId, connection) => {
-  const register = await safelyCreateAccount(programId);
+  const register = new Account();
   let transaction = new Transaction();
   const lamports = await connection.getMinimumBalanceForRentExemption(space);
   transaction.add(SystemProgram.createAccount({
@@ -4,17 +4,537 @@ const {
   Transaction,
   Account,
   PublicKey,
+  SystemProgram,
   SYSVAR_RENT_PUBKEY,
+  SYSVAR_CLOCK_PUBKEY,
 } = require('@solana/web3.js');
 const soproxABI = require('soprox-abi');
 const { establishConnection, loadPayer } = require('../../lib/network');
 const store = require('../../lib/store');
 
+// Highest fee the wraps and unwraps below accept, the tests set 30 bps
+const MAX_FEE_BPS = 30;
+
+/**
+ * Constructor
+ */
+const constructor = async (
+  symbol,
+  decimals,
+  wrapper,
+  tokenOwnerPublicKey,
+  src20_treasury,
+  src20_token,
+  src20ProgramId,
+  spl_treasury,
+  splTokenPublickey,
+  splProgramId,
+  src20_fee_vault,
+  spl_fee_vault,
+  registryPublicKey,
+  programId,
+  payer,
+  connection
+) => {
+  console.log('Calling Constructor to', wrapper.publicKey.toBase58());
+  const schema = [
+    { key: 'code', type: 'u8' },
+    { key: 'symbol', type: '[char;4]' },
+    { key: 'decimals', type: 'u8' },
+  ]
+  const layout = new soproxABI.struct(schema, {
+    code: 0,
+    symbol,
+    decimals,
+  });
+  const instruction = new TransactionInstruction({
+    keys: [
+      { pubkey: wrapper.publicKey, isSigner: true, isWritable: true },
+      { pubkey: tokenOwnerPublicKey, isSigner: false, isWritable: false },
+      { pubkey: src20_treasury.publicKey, isSigner: true, isWritable: true },
+      { pubkey: src20_token.publicKey, isSigner: true, isWritable: true },
+      { pubkey: src20ProgramId, isSigner: false, isWritable: false },
+      { pubkey: spl_treasury.publicKey, isSigner: false, isWritable: true },
+      { pubkey: splTokenPublickey, isSigner: false, isWritable: false },
+      { pubkey: splProgramId, isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+      { pubkey: payer.publicKey, isSigner: false, isWritable: false },
+      { pubkey: src20_fee_vault.publicKey, isSigner: true, isWritable: true },
+      { pubkey: spl_fee_vault.publicKey, isSigner: false, isWritable: true },
+      { pubkey: payer.publicKey, isSigner: true, isWritable: true },
+      { pubkey: registryPublicKey, isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: payer.publicKey, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: layout.toBuffer()
+  });
+  const transaction = new Transaction();
+  transaction.add(instruction);
+  await sendAndConfirmTransaction(
+    connection,
+    transaction,
+    [
+      payer,
+      new Account(Buffer.from(wrapper.secretKey, 'hex')),
+      new Account(Buffer.from(src20_treasury.secretKey, 'hex')),
+      new Account(Buffer.from(src20_token.secretKey, 'hex')),
+      new Account(Buffer.from(src20_fee_vault.secretKey, 'hex')),
+    ],
+    {
+      skipPreflight: true,
+      commitment: 'recent',
+    });
+}
+
+/**
+ * Wrap
+ */
+const unwrap = async (
+  amount,
+  src_acc,
+  dst_acc,
+  wrapper,
+  tokenOwnerPublicKey,
+  src20_treasury,
+  src20_token,
+  src20ProgramId,
+  spl_treasury,
+  splTokenPublickey,
+  splProgramId,
+  fee_vault,
+  programId,
+  payer,
+  connection
+) => {
+  console.log('Calling Unwrap to', wrapper.publicKey.toBase58());
+  const schema = [
+    { key: 'code', type: 'u8' },
+    { key: 'amount', type: 'u64' },
+    { key: 'max_fee_bps', type: 'u16' },
+  ]
+  const layout = new soproxABI.struct(schema, {
+    code: 2,
+    amount,
+    max_fee_bps: MAX_FEE_BPS,
+  });
+  const instruction = new TransactionInstruction({
+    keys: [
+      { pubkey: payer.publicKey, isSigner: true, isWritable: false },
+      { pubkey: src_acc.publicKey, isSigner: false, isWritable: true },
+      { pubkey: dst_acc.publicKey, isSigner: false, isWritable: true },
+      { pubkey: wrapper.publicKey, isSigner: false, isWritable: true },
+      { pubkey: tokenOwnerPublicKey, isSigner: false, isWritable: false },
+      { pubkey: src20_treasury.publicKey, isSigner: false, isWritable: true },
+      { pubkey: src20_token.publicKey, isSigner: false, isWritable: true },
+      { pubkey: src20ProgramId, isSigner: false, isWritable: false },
+      { pubkey: spl_treasury.publicKey, isSigner: false, isWritable: true },
+      { pubkey: splTokenPublickey, isSigner: false, isWritable: false },
+      { pubkey: splProgramId, isSigner: false, isWritable: false },
+      { pubkey: fee_vault.publicKey, isSigner: false, isWritable: true },
+      { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: layout.toBuffer()
+  });
+  const transaction = new Transaction();
+  transaction.add(instruction);
+  await sendAndConfirmTransaction(
+    connection,
+    transaction,
+    [payer],
+    {
+      skipPreflight: true,
+      commitment: 'recent',
+    });
+}
+
+/**
+ * Approve an SPL delegate
+ */
+const approveSPL = async (
+  amount,
+  srcPublicKey,
+  delegatePublicKey,
+  splProgramId,
+  payer,
+  connection
+) => {
+  console.log('Calling Approve to', delegatePublicKey.toBase58());
+  const schema = [
+    { key: 'code', type: 'u8' },
+    { key: 'amount', type: 'u64' },
+  ]
+  const layout = new soproxABI.struct(schema, {
+    code: 4,
+    amount,
+  });
+  const instruction = new TransactionInstruction({
+    keys: [
+      { pubkey: srcPublicKey, isSigner: false, isWritable: true },
+      { pubkey: delegatePublicKey, isSigner: false, isWritable: false },
+      { pubkey: payer.publicKey, isSigner: true, isWritable: false },
+    ],
+    programId: splProgramId,
+    data: layout.toBuffer()
+  });
+  const transaction = new Transaction();
+  transaction.add(instruction);
+  await sendAndConfirmTransaction(
+    connection,
+    transaction,
+    [payer],
+    {
+      skipPreflight: true,
+      commitment: 'recent',
+    });
+}
+
+/**
+ * Init SPL Account
+ */
+const initSPLAccount = async (
+  splAccount,
+  splTokenPublickey,
+  programId,
+  payer,
+  connection
+) => {
+  console.log('Calling InitializeAccount to', programId.toBase58());
+  const schema = [
+    { key: 'code', type: 'u8' },
+  ]
+  const layout = new soproxABI.struct(schema, {
+    code: 1,
+  });
+  const instruction = new TransactionInstruction({
+    keys: [
+      { pubkey: splAccount.publicKey, isSigner: false, isWritable: true },
+      { pubkey: splTokenPublickey, isSigner: false, isWritable: false },
+      { pubkey: payer.publicKey, isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: layout.toBuffer()
+  });
+  const transaction = new Transaction();
+  transaction.add(instruction);
+  await sendAndConfirmTransaction(
+    connection,
+    transaction,
+    [payer],
+    {
+      skipPreflight: true,
+      commitment: 'recent',
+    });
+}
+
+/**
+ * Wrap
+ */
+const wrap = async (
+  amount,
+  srcPublicKey,
+  dst_acc,
+  wrapper,
+  tokenOwnerPublicKey,
+  src20_treasury,
+  src20_token,
+  src20ProgramId,
+  spl_treasury,
+  splTokenPublickey,
+  splProgramId,
+  fee_vault,
+  programId,
+  payer,
+  connection,
+  fromDelegate = false
+) => {
+  console.log(`Calling ${fromDelegate ? 'WrapFromDelegate' : 'Wrap'} to`, wrapper.publicKey.toBase58());
+  const schema = [
+    { key: 'code', type: 'u8' },
+    { key: 'amount', type: 'u64' },
+    { key: 'max_fee_bps', type: 'u16' },
+  ]
+  const layout = new soproxABI.struct(schema, {
+    code: fromDelegate ? 9 : 1,
+    amount,
+    max_fee_bps: MAX_FEE_BPS,
+  });
+  const instruction = new TransactionInstruction({
+    keys: [
+      { pubkey: payer.publicKey, isSigner: true, isWritable: true },
+      { pubkey: srcPublicKey, isSigner: false, isWritable: true },
+      { pubkey: dst_acc.publicKey, isSigner: false, isWritable: true },
+      { pubkey: wrapper.publicKey, isSigner: false, isWritable: true },
+      { pubkey: tokenOwnerPublicKey, isSigner: false, isWritable: false },
+      { pubkey: src20_treasury.publicKey, isSigner: false, isWritable: true },
+      { pubkey: src20_token.publicKey, isSigner: false, isWritable: true },
+      { pubkey: src20ProgramId, isSigner: false, isWritable: false },
+      { pubkey: spl_treasury.publicKey, isSigner: false, isWritable: true },
+      { pubkey: splTokenPublickey, isSigner: false, isWritable: false },
+      { pubkey: splProgramId, isSigner: false, isWritable: false },
+      { pubkey: fee_vault.publicKey, isSigner: false, isWritable: true },
+      { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
+      // Only used to open a missing destination
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: layout.toBuffer()
+  });
+  const transaction = new Transaction();
+  transaction.add(instruction);
+  await sendAndConfirmTransaction(
+    connection,
+    transaction,
+    [payer],
+    {
+      skipPreflight: true,
+      commitment: 'recent',
+    });
+}
+
+/**
+ * Set fee
+ */
+const setFee = async (
+  fee_bps,
+  wrapper,
+  programId,
+  payer,
+  connection
+) => {
+  console.log('Calling SetFee to', wrapper.publicKey.toBase58());
+  const schema = [
+    { key: 'code', type: 'u8' },
+    { key: 'fee_bps', type: 'u16' },
+  ]
+  const layout = new soproxABI.struct(schema, {
+    code: 4,
+    fee_bps,
+  });
+  const instruction = new TransactionInstruction({
+    keys: [
+      { pubkey: payer.publicKey, isSigner: true, isWritable: false },
+      { pubkey: wrapper.publicKey, isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: layout.toBuffer()
+  });
+  const transaction = new Transaction();
+  transaction.add(instruction);
+  await sendAndConfirmTransaction(
+    connection,
+    transaction,
+    [payer],
+    {
+      skipPreflight: true,
+      commitment: 'recent',
+    });
+}
+
+/**
+ * Set rate limit
+ */
+const setRateLimit = async (
+  window_duration,
+  wrap_limit,
+  unwrap_limit,
+  wrapper,
+  programId,
+  payer,
+  connection
+) => {
+  console.log('Calling SetRateLimit to', wrapper.publicKey.toBase58());
+  const schema = [
+    { key: 'code', type: 'u8' },
+    { key: 'window_duration', type: 'i64' },
+    { key: 'wrap_limit', type: 'u64' },
+    { key: 'unwrap_limit', type: 'u64' },
+  ]
+  const layout = new soproxABI.struct(schema, {
+    code: 17,
+    window_duration,
+    wrap_limit,
+    unwrap_limit,
+  });
+  const instruction = new TransactionInstruction({
+    keys: [
+      { pubkey: payer.publicKey, isSigner: true, isWritable: false },
+      { pubkey: wrapper.publicKey, isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: layout.toBuffer()
+  });
+  const transaction = new Transaction();
+  transaction.add(instruction);
+  await sendAndConfirmTransaction(
+    connection,
+    transaction,
+    [payer],
+    {
+      skipPreflight: true,
+      commitment: 'recent',
+    });
+}
+
+/**
+ * Withdraw fee
+ */
+const withdrawFee = async (
+  src20_amount,
+  spl_amount,
+  wrapper,
+  tokenOwnerPublicKey,
+  src20_fee_vault,
+  src20_dst,
+  src20_token,
+  src20ProgramId,
+  spl_fee_vault,
+  spl_dst,
+  splProgramId,
+  programId,
+  payer,
+  connection
+) => {
+  console.log('Calling WithdrawFee to', wrapper.publicKey.toBase58());
+  const schema = [
+    { key: 'code', type: 'u8' },
+    { key: 'src20_amount', type: 'u64' },
+    { key: 'spl_amount', type: 'u64' },
+  ]
+  const layout = new soproxABI.struct(schema, {
+    code: 5,
+    src20_amount,
+    spl_amount,
+  });
+  const instruction = new TransactionInstruction({
+    keys: [
+      { pubkey: payer.publicKey, isSigner: true, isWritable: false },
+      { pubkey: wrapper.publicKey, isSigner: false, isWritable: false },
+      { pubkey: tokenOwnerPublicKey, isSigner: false, isWritable: false },
+      { pubkey: src20_fee_vault.publicKey, isSigner: false, isWritable: true },
+      { pubkey: src20_dst.publicKey, isSigner: false, isWritable: true },
+      { pubkey: src20_token.publicKey, isSigner: false, isWritable: false },
+      { pubkey: src20ProgramId, isSigner: false, isWritable: false },
+      { pubkey: spl_fee_vault.publicKey, isSigner: false, isWritable: true },
+      { pubkey: spl_dst.publicKey, isSigner: false, isWritable: true },
+      { pubkey: splProgramId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: layout.toBuffer()
+  });
+  const transaction = new Transaction();
+  transaction.add(instruction);
+  await sendAndConfirmTransaction(
+    connection,
+    transaction,
+    [payer],
+    {
+      skipPreflight: true,
+      commitment: 'recent',
+    });
+}
+
+/**
+ * Pause or resume
+ */
+const setPaused = async (
+  paused,
+  wrapper,
+  programId,
+  payer,
+  connection
+) => {
+  console.log(`Calling ${paused ? 'PauseWrapper' : 'ResumeWrapper'} to`, wrapper.publicKey.toBase58());
+  const schema = [
+    { key: 'code', type: 'u8' },
+  ]
+  const layout = new soproxABI.struct(schema, {
+    code: paused ? 6 : 7,
+  });
+  const instruction = new TransactionInstruction({
+    keys: [
+      { pubkey: payer.publicKey, isSigner: true, isWritable: false },
+      { pubkey: wrapper.publicKey, isSigner: false, isWritable: true },
+    ],
+    programId,
+    data: layout.toBuffer()
+  });
+  const transaction = new Transaction();
+  transaction.add(instruction);
+  await sendAndConfirmTransaction(
+    connection,
+    transaction,
+    [payer],
+    {
+      skipPreflight: true,
+      commitment: 'recent',
+    });
+}
+
+/**
+ * Decommission
+ */
+const decommission = async (
+  wrapper,
+  tokenOwnerPublicKey,
+  src20_treasury,
+  src20_fee_vault,
+  src20_token,
+  src20ProgramId,
+  spl_treasury,
+  spl_fee_vault,
+  spl_dst,
+  splProgramId,
+  programId,
+  payer,
+  connection
+) => {
+  console.log('Calling DecommissionWrapper to', wrapper.publicKey.toBase58());
+  const schema = [
+    { key: 'code', type: 'u8' },
+  ]
+  const layout = new soproxABI.struct(schema, {
+    code: 8,
+  });
+  const instruction = new TransactionInstruction({
+    keys: [
+      { pubkey: payer.publicKey, isSigner: true, isWritable: true },
+      { pubkey: wrapper.publicKey, isSigner: false, isWritable: true },
+      { pubkey: tokenOwnerPublicKey, isSigner: false, isWritable: true },
+      { pubkey: src20_treasury.publicKey, isSigner: false, isWritable: true },
+      { pubkey: src20_fee_vault.publicKey, isSigner: false, isWritable: true },
+      { pubkey: src20_token.publicKey, isSigner: false, isWritable: true },
+      { pubkey: src20ProgramId, isSigner: false, isWritable: false },
+      { pubkey: spl_treasury.publicKey, isSigner: false, isWritable: true },
+      { pubkey: spl_fee_vault.publicKey, isSigner: false, isWritable: true },
+      { pubkey: spl_dst.publicKey, isSigner: false, isWritable: true },
+      { pubkey: splProgramId, isSigner: false, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+    ],
+    programId,
+    data: layout.toBuffer()
+  });
+  const transaction = new Transaction();
+  transaction.add(instruction);
+  await sendAndConfirmTransaction(
+    connection,
+    transaction,
+    [payer],
+    {
+      skipPreflight: true,
+      commitment: 'recent',
+    });
+}
+
 /**
- * Constructor
+ * Wrap SOL
  */
-const constructor = async (
-  symbol,
+const wrapSol = async (
+  lamports,
+  dst_acc,
   wrapper,
   tokenOwnerPublicKey,
   src20_treasury,
@@ -23,30 +543,37 @@ const constructor = async (
   spl_treasury,
   splTokenPublickey,
   splProgramId,
+  fee_vault,
   programId,
   payer,
   connection
 ) => {
-  console.log('Calling Constructor to', wrapper.publicKey.toBase58());
+  console.log('Calling WrapSol to', wrapper.publicKey.toBase58());
   const schema = [
     { key: 'code', type: 'u8' },
-    { key: 'symbol', type: '[char;4]' },
+    { key: 'lamports', type: 'u64' },
+    { key: 'max_fee_bps', type: 'u16' },
   ]
   const layout = new soproxABI.struct(schema, {
-    code: 0,
-    symbol,
+    code: 10,
+    lamports,
+    max_fee_bps: MAX_FEE_BPS,
   });
   const instruction = new TransactionInstruction({
     keys: [
-      { pubkey: wrapper.publicKey, isSigner: true, isWritable: true },
+      { pubkey: payer.publicKey, isSigner: true, isWritable: true },
+      { pubkey: dst_acc.publicKey, isSigner: false, isWritable: true },
+      { pubkey: wrapper.publicKey, isSigner: false, isWritable: true },
       { pubkey: tokenOwnerPublicKey, isSigner: false, isWritable: false },
-      { pubkey: src20_treasury.publicKey, isSigner: true, isWritable: true },
-      { pubkey: src20_token.publicKey, isSigner: true, isWritable: true },
+      { pubkey: src20_treasury.publicKey, isSigner: false, isWritable: true },
+      { pubkey: src20_token.publicKey, isSigner: false, isWritable: true },
       { pubkey: src20ProgramId, isSigner: false, isWritable: false },
       { pubkey: spl_treasury.publicKey, isSigner: false, isWritable: true },
       { pubkey: splTokenPublickey, isSigner: false, isWritable: false },
       { pubkey: splProgramId, isSigner: false, isWritable: false },
-      { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+      { pubkey: fee_vault.publicKey, isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
     ],
     programId,
     data: layout.toBuffer()
@@ -56,12 +583,80 @@ const constructor = async (
   await sendAndConfirmTransaction(
     connection,
     transaction,
-    [
-      payer,
-      new Account(Buffer.from(wrapper.secretKey, 'hex')),
-      new Account(Buffer.from(src20_treasury.secretKey, 'hex')),
-      new Account(Buffer.from(src20_token.secretKey, 'hex')),
+    [payer],
+    {
+      skipPreflight: true,
+      commitment: 'recent',
+    });
+}
+
+/**
+ * Unwrap to SOL
+ */
+const unwrapToSol = async (
+  amount,
+  src_acc,
+  wrapper,
+  tokenOwnerPublicKey,
+  src20_treasury,
+  src20_token,
+  src20ProgramId,
+  spl_treasury,
+  splTokenPublickey,
+  splProgramId,
+  fee_vault,
+  programId,
+  payer,
+  connection
+) => {
+  console.log('Calling UnwrapToSol to', wrapper.publicKey.toBase58());
+  const schema = [
+    { key: 'code', type: 'u8' },
+    { key: 'amount', type: 'u64' },
+    { key: 'max_fee_bps', type: 'u16' },
+  ]
+  const layout = new soproxABI.struct(schema, {
+    code: 11,
+    amount,
+    max_fee_bps: MAX_FEE_BPS,
+  });
+  // A fresh native account, closed to the payer within the instruction
+  const temporary = new Account();
+  const space = 165;
+  const rent = await connection.getMinimumBalanceForRentExemption(space);
+  const instruction = new TransactionInstruction({
+    keys: [
+      { pubkey: payer.publicKey, isSigner: true, isWritable: true },
+      { pubkey: src_acc.publicKey, isSigner: false, isWritable: true },
+      { pubkey: wrapper.publicKey, isSigner: false, isWritable: true },
+      { pubkey: tokenOwnerPublicKey, isSigner: false, isWritable: false },
+      { pubkey: src20_treasury.publicKey, isSigner: false, isWritable: true },
+      { pubkey: src20_token.publicKey, isSigner: false, isWritable: true },
+      { pubkey: src20ProgramId, isSigner: false, isWritable: false },
+      { pubkey: spl_treasury.publicKey, isSigner: false, isWritable: true },
+      { pubkey: splTokenPublickey, isSigner: false, isWritable: false },
+      { pubkey: splProgramId, isSigner: false, isWritable: false },
+      { pubkey: fee_vault.publicKey, isSigner: false, isWritable: true },
+      { pubkey: temporary.publicKey, isSigner: false, isWritable: true },
+      { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
     ],
+    programId,
+    data: layout.toBuffer()
+  });
+  const transaction = new Transaction();
+  transaction.add(SystemProgram.createAccount({
+    fromPubkey: payer.publicKey,
+    newAccountPubkey: temporary.publicKey,
+    lamports: rent,
+    space,
+    programId: splProgramId,
+  }));
+  transaction.add(instruction);
+  await sendAndConfirmTransaction(
+    connection,
+    transaction,
+    [payer, temporary],
     {
       skipPreflight: true,
       commitment: 'recent',
@@ -69,27 +664,37 @@ const constructor = async (
 }
 
 /**
- * Init SRC20 Account
+ * Reverse constructor
  */
-const initSRC20Account = async (
-  src20Account,
-  src20Token,
+const reverseConstructor = async (
+  wrapper,
+  tokenOwnerPublicKey,
+  src20_treasury,
+  src20_token,
+  src20ProgramId,
+  spl_token,
+  splProgramId,
   programId,
   payer,
   connection
 ) => {
-  console.log('Calling AccountConstructor to', programId.toBase58());
+  console.log('Calling ReverseConstructor to', wrapper.publicKey.toBase58());
   const schema = [
     { key: 'code', type: 'u8' },
   ]
   const layout = new soproxABI.struct(schema, {
-    code: 1,
+    code: 12,
   });
   const instruction = new TransactionInstruction({
     keys: [
-      { pubkey: payer.publicKey, isSigner: true, isWritable: false },
-      { pubkey: src20Token.publicKey, isSigner: false, isWritable: false },
-      { pubkey: src20Account.publicKey, isSigner: true, isWritable: true },
+      { pubkey: wrapper.publicKey, isSigner: true, isWritable: true },
+      { pubkey: tokenOwnerPublicKey, isSigner: false, isWritable: false },
+      { pubkey: src20_treasury.publicKey, isSigner: true, isWritable: true },
+      { pubkey: src20_token.publicKey, isSigner: false, isWritable: false },
+      { pubkey: src20ProgramId, isSigner: false, isWritable: false },
+      { pubkey: spl_token.publicKey, isSigner: false, isWritable: true },
+      { pubkey: splProgramId, isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
     ],
     programId,
     data: layout.toBuffer()
@@ -101,7 +706,8 @@ const initSRC20Account = async (
     transaction,
     [
       payer,
-      new Account(Buffer.from(src20Account.secretKey, 'hex')),
+      new Account(Buffer.from(wrapper.secretKey, 'hex')),
+      new Account(Buffer.from(src20_treasury.secretKey, 'hex')),
     ],
     {
       skipPreflight: true,
@@ -110,9 +716,10 @@ const initSRC20Account = async (
 }
 
 /**
- * Wrap
+ * Reverse wrap and unwrap
  */
-const unwrap = async (
+const reverseSwap = async (
+  code,
   amount,
   src_acc,
   dst_acc,
@@ -121,20 +728,19 @@ const unwrap = async (
   src20_treasury,
   src20_token,
   src20ProgramId,
-  spl_treasury,
-  splTokenPublickey,
+  spl_token,
   splProgramId,
   programId,
   payer,
   connection
 ) => {
-  console.log('Calling Unwrap to', wrapper.publicKey.toBase58());
+  console.log(`Calling ${code == 13 ? 'ReverseWrap' : 'ReverseUnwrap'} to`, wrapper.publicKey.toBase58());
   const schema = [
     { key: 'code', type: 'u8' },
     { key: 'amount', type: 'u64' },
   ]
   const layout = new soproxABI.struct(schema, {
-    code: 2,
+    code,
     amount,
   });
   const instruction = new TransactionInstruction({
@@ -147,8 +753,7 @@ const unwrap = async (
       { pubkey: src20_treasury.publicKey, isSigner: false, isWritable: true },
       { pubkey: src20_token.publicKey, isSigner: false, isWritable: false },
       { pubkey: src20ProgramId, isSigner: false, isWritable: false },
-      { pubkey: spl_treasury.publicKey, isSigner: false, isWritable: true },
-      { pubkey: splTokenPublickey, isSigner: false, isWritable: false },
+      { pubkey: spl_token.publicKey, isSigner: false, isWritable: true },
       { pubkey: splProgramId, isSigner: false, isWritable: false },
     ],
     programId,
@@ -167,28 +772,57 @@ const unwrap = async (
 }
 
 /**
- * Init SPL Account
+ * Bootstrap
  */
-const initSPLAccount = async (
-  splAccount,
+const bootstrap = async (
+  symbol,
+  decimals,
   splTokenPublickey,
+  src20ProgramId,
+  splProgramId,
   programId,
   payer,
   connection
 ) => {
-  console.log('Calling InitializeAccount to', programId.toBase58());
+  const wrapper = new Account();
+  console.log('Calling Bootstrap to', wrapper.publicKey.toBase58());
+  const derive = async (seeds) => (await PublicKey.findProgramAddress(seeds, programId))[0];
+  const seed = wrapper.publicKey.toBuffer();
+  const tokenOwnerPublicKey = await derive([seed]);
+  const src20TreasuryPublicKey = await derive([seed, Buffer.from('src20_treasury')]);
+  const src20TokenPublicKey = await derive([seed, Buffer.from('src20_token')]);
+  const src20FeeVaultPublicKey = await derive([seed, Buffer.from('src20_fee_vault')]);
+  const splTreasuryPublicKey = await derive([seed, Buffer.from('spl_treasury')]);
+  const splFeeVaultPublicKey = await derive([seed, Buffer.from('spl_fee_vault')]);
+  const registryPublicKey = await derive([splTokenPublickey.toBuffer(), src20ProgramId.toBuffer()]);
   const schema = [
     { key: 'code', type: 'u8' },
+    { key: 'symbol', type: '[char;4]' },
+    { key: 'decimals', type: 'u8' },
   ]
   const layout = new soproxABI.struct(schema, {
-    code: 1,
+    code: 15,
+    symbol,
+    decimals,
   });
   const instruction = new TransactionInstruction({
     keys: [
-      { pubkey: splAccount.publicKey, isSigner: false, isWritable: true },
+      { pubkey: wrapper.publicKey, isSigner: true, isWritable: true },
+      { pubkey: tokenOwnerPublicKey, isSigner: false, isWritable: false },
+      { pubkey: src20TreasuryPublicKey, isSigner: false, isWritable: true },
+      { pubkey: src20TokenPublicKey, isSigner: false, isWritable: true },
+      { pubkey: src20ProgramId, isSigner: false, isWritable: false },
+      { pubkey: splTreasuryPublicKey, isSigner: false, isWritable: true },
       { pubkey: splTokenPublickey, isSigner: false, isWritable: false },
-      { pubkey: payer.publicKey, isSigner: false, isWritable: false },
+      { pubkey: splProgramId, isSigner: false, isWritable: false },
       { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+      { pubkey: payer.publicKey, isSigner: false, isWritable: false },
+      { pubkey: src20FeeVaultPublicKey, isSigner: false, isWritable: true },
+      { pubkey: splFeeVaultPublicKey, isSigner: false, isWritable: true },
+      { pubkey: payer.publicKey, isSigner: true, isWritable: true },
+      { pubkey: registryPublicKey, isSigner: false, isWritable: true },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: payer.publicKey, isSigner: false, isWritable: false },
     ],
     programId,
     data: layout.toBuffer()
@@ -198,61 +832,46 @@ const initSPLAccount = async (
   await sendAndConfirmTransaction(
     connection,
     transaction,
-    [payer],
+    [payer, wrapper],
     {
       skipPreflight: true,
       commitment: 'recent',
     });
+  return wrapper;
 }
 
 /**
- * Wrap
+ * Prove reserves
  */
-const wrap = async (
-  amount,
-  srcPublicKey,
-  dst_acc,
+const proveReserves = async (
   wrapper,
-  tokenOwnerPublicKey,
   src20_treasury,
   src20_token,
-  src20ProgramId,
   spl_treasury,
-  splTokenPublickey,
-  splProgramId,
   programId,
   payer,
   connection
 ) => {
-  console.log('Calling Wrap to', wrapper.publicKey.toBase58());
+  console.log('Calling ProveReserves to', wrapper.publicKey.toBase58());
   const schema = [
     { key: 'code', type: 'u8' },
-    { key: 'amount', type: 'u64' },
   ]
   const layout = new soproxABI.struct(schema, {
-    code: 1,
-    amount,
+    code: 16,
   });
   const instruction = new TransactionInstruction({
     keys: [
-      { pubkey: payer.publicKey, isSigner: true, isWritable: false },
-      { pubkey: srcPublicKey, isSigner: false, isWritable: true },
-      { pubkey: dst_acc.publicKey, isSigner: false, isWritable: true },
       { pubkey: wrapper.publicKey, isSigner: false, isWritable: false },
-      { pubkey: tokenOwnerPublicKey, isSigner: false, isWritable: false },
-      { pubkey: src20_treasury.publicKey, isSigner: false, isWritable: true },
+      { pubkey: src20_treasury.publicKey, isSigner: false, isWritable: false },
       { pubkey: src20_token.publicKey, isSigner: false, isWritable: false },
-      { pubkey: src20ProgramId, isSigner: false, isWritable: false },
-      { pubkey: spl_treasury.publicKey, isSigner: false, isWritable: true },
-      { pubkey: splTokenPublickey, isSigner: false, isWritable: false },
-      { pubkey: splProgramId, isSigner: false, isWritable: false },
+      { pubkey: spl_treasury.publicKey, isSigner: false, isWritable: false },
     ],
     programId,
     data: layout.toBuffer()
   });
   const transaction = new Transaction();
   transaction.add(instruction);
-  await sendAndConfirmTransaction(
+  const txId = await sendAndConfirmTransaction(
     connection,
     transaction,
     [payer],
@@ -260,9 +879,10 @@ const wrap = async (
       skipPreflight: true,
       commitment: 'recent',
     });
+  const { meta: { logMessages } } = await connection.getConfirmedTransaction(txId);
+  console.log(logMessages.find(log => log.includes('ProveReserves:')));
 }
 
-
 /**
  * Report the number of times the greeted account has been said hello to
  */
@@ -290,18 +910,32 @@ const main = async () => {
   console.log("Let's say hello to a Solana account...");
   const {
     connection, payer, programId,
-    registers: [wrapper, src20_treasury, src20_acc, src20_token, spl_treasury, spl_acc]
+    registers: [
+      wrapper, src20_treasury, src20_register, src20_token, spl_treasury, spl_acc, src20_fee_vault, spl_fee_vault,
+      reverse_wrapper, reverse_src20_treasury, reverse_spl, reverse_spl_acc,
+    ]
   } = await init();
   const seeds = [wrapper.publicKey.toBuffer()];
-  const tokenOwnerPublicKey = await PublicKey.createProgramAddress(seeds, programId);
+  const [tokenOwnerPublicKey] = await PublicKey.findProgramAddress(seeds, programId);
   const src20ProgramId = new PublicKey('G3JuvCS4Q6u8B9QtHPRyAEBSvggfxQySrzWB1YNF5i1v');
   const splBudgetPublicKey = new PublicKey('Cif4LGs1x7Bch3qNz331Z1w8dw3jqQpcPjWZWxpsxsr1');
   const splTokenPublickey = new PublicKey('FAA9xNJzgwsy2Awd2AzMigoF8rTr4E6nUrE8ohdBfs6b');
   const splProgramId = new PublicKey('TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA');
+  const [registryPublicKey] = await PublicKey.findProgramAddress(
+    [splTokenPublickey.toBuffer(), src20ProgramId.toBuffer()],
+    programId
+  );
+  // Wrap opens the src20 account of the payer at this address
+  const [src20AccPublicKey] = await PublicKey.findProgramAddress(
+    [Buffer.from('destination'), payer.publicKey.toBuffer(), src20_token.publicKey.toBuffer()],
+    programId
+  );
+  const src20_acc = { publicKey: src20AccPublicKey, schema: src20_register.schema };
 
   console.log('\nTest Constructor');
   await constructor(
     ['S', 'P', 'X', '-'],
+    9,
     wrapper,
     tokenOwnerPublicKey,
     src20_treasury,
@@ -310,21 +944,54 @@ const main = async () => {
     spl_treasury,
     splTokenPublickey,
     splProgramId,
+    src20_fee_vault,
+    spl_fee_vault,
+    registryPublicKey,
     programId,
     payer,
     connection
   );
   console.log('Wrapper data:', await info(wrapper, connection));
+  const registry = { publicKey: registryPublicKey, schema: [{ key: 'wrapper', type: 'pub' }, { key: 'initialized', type: 'bool' }] };
+  console.log('Registry data:', await info(registry, connection));
+
+  console.log('\nTest SetFee');
+  await setFee(30, wrapper, programId, payer, connection);
+  console.log('Wrapper data:', await info(wrapper, connection));
+
+  console.log('\nTest SetRateLimit');
+  await setRateLimit(3600n, 10000000000n, 10000000000n, wrapper, programId, payer, connection);
+  console.log('Wrapper data:', await info(wrapper, connection));
 
   console.log('\nTest Wrap');
-  await initSRC20Account(
+  await wrap(
+    1000000000n,
+    splBudgetPublicKey,
     src20_acc,
+    wrapper,
+    tokenOwnerPublicKey,
+    src20_treasury,
     src20_token,
     src20ProgramId,
+    spl_treasury,
+    splTokenPublickey,
+    splProgramId,
+    src20_fee_vault,
+    programId,
+    payer,
+    connection
+  );
+  console.log('SRC20 new data:', await info(src20_acc, connection));
+
+  console.log('\nTest WrapFromDelegate');
+  await approveSPL(
+    1000000000n,
+    splBudgetPublicKey,
+    tokenOwnerPublicKey,
+    splProgramId,
     payer,
     connection
   );
-  console.log('SRC20 current data:', await info(src20_acc, connection));
   await wrap(
     1000000000n,
     splBudgetPublicKey,
@@ -337,9 +1004,11 @@ const main = async () => {
     spl_treasury,
     splTokenPublickey,
     splProgramId,
+    src20_fee_vault,
     programId,
     payer,
-    connection
+    connection,
+    true
   );
   console.log('SRC20 new data:', await info(src20_acc, connection));
 
@@ -364,11 +1033,189 @@ const main = async () => {
     spl_treasury,
     splTokenPublickey,
     splProgramId,
+    spl_fee_vault,
+    programId,
+    payer,
+    connection
+  );
+  console.log('SPL new data:', await info(spl_acc, connection));
+
+  console.log('\nTest WithdrawFee');
+  console.log('SRC20 fee vault data:', await info(src20_fee_vault, connection));
+  console.log('SPL fee vault data:', await info(spl_fee_vault, connection));
+  await withdrawFee(
+    1000000n,
+    1000000n,
+    wrapper,
+    tokenOwnerPublicKey,
+    src20_fee_vault,
+    src20_acc,
+    src20_token,
+    src20ProgramId,
+    spl_fee_vault,
+    spl_acc,
+    splProgramId,
     programId,
     payer,
     connection
   );
+  console.log('SRC20 new data:', await info(src20_acc, connection));
   console.log('SPL new data:', await info(spl_acc, connection));
+
+  console.log('\nTest ProveReserves');
+  await proveReserves(
+    wrapper,
+    src20_treasury,
+    src20_token,
+    spl_treasury,
+    programId,
+    payer,
+    connection
+  );
+
+  console.log('\nTest WrapSol');
+  try {
+    await wrapSol(
+      1000000n,
+      src20_acc,
+      wrapper,
+      tokenOwnerPublicKey,
+      src20_treasury,
+      src20_token,
+      src20ProgramId,
+      spl_treasury,
+      splTokenPublickey,
+      splProgramId,
+      src20_fee_vault,
+      programId,
+      payer,
+      connection
+    );
+    await unwrapToSol(
+      1000000n,
+      src20_acc,
+      wrapper,
+      tokenOwnerPublicKey,
+      src20_treasury,
+      src20_token,
+      src20ProgramId,
+      spl_treasury,
+      splTokenPublickey,
+      splProgramId,
+      spl_fee_vault,
+      programId,
+      payer,
+      connection
+    );
+  } catch (er) {
+    // Only wrappers of the native mint take SOL
+    console.log('The wrapper is not a native wrapper');
+  }
+
+  console.log('\nTest ReverseWrap');
+  const [reverseTokenOwnerPublicKey] = await PublicKey.findProgramAddress(
+    [reverse_wrapper.publicKey.toBuffer()],
+    programId
+  );
+  await reverseConstructor(
+    reverse_wrapper,
+    reverseTokenOwnerPublicKey,
+    reverse_src20_treasury,
+    src20_token,
+    src20ProgramId,
+    reverse_spl,
+    splProgramId,
+    programId,
+    payer,
+    connection
+  );
+  console.log('Reverse wrapper data:', await info(reverse_wrapper, connection));
+  await initSPLAccount(
+    reverse_spl_acc,
+    reverse_spl.publicKey,
+    splProgramId,
+    payer,
+    connection
+  );
+  for (const [code, src, dst] of [[13, src20_acc, reverse_spl_acc], [14, reverse_spl_acc, src20_acc]]) {
+    await reverseSwap(
+      code,
+      100000000n,
+      src,
+      dst,
+      reverse_wrapper,
+      reverseTokenOwnerPublicKey,
+      reverse_src20_treasury,
+      src20_token,
+      src20ProgramId,
+      reverse_spl,
+      splProgramId,
+      programId,
+      payer,
+      connection
+    );
+    console.log('SRC20 new data:', await info(src20_acc, connection));
+    console.log('SPL new data:', await info(reverse_spl_acc, connection));
+  }
+
+  console.log('\nTest Bootstrap');
+  const bootstrapped = await bootstrap(
+    ['R', 'E', 'V', '-'],
+    9,
+    reverse_spl.publicKey,
+    src20ProgramId,
+    splProgramId,
+    programId,
+    payer,
+    connection
+  );
+  console.log('Bootstrapped wrapper data:', await info({ ...wrapper, publicKey: bootstrapped.publicKey }, connection));
+
+  console.log('\nTest PauseWrapper');
+  await setPaused(true, wrapper, programId, payer, connection);
+  console.log('Wrapper data:', await info(wrapper, connection));
+  await setPaused(false, wrapper, programId, payer, connection);
+  console.log('Wrapper data:', await info(wrapper, connection));
+
+  console.log('\nTest DecommissionWrapper');
+  // Unwrap what circulates, the fees collected by the wrapper must not block it
+  const { amount: circulating } = await info(src20_acc, connection);
+  if (BigInt(circulating) > 0n) await unwrap(
+    BigInt(circulating),
+    src20_acc,
+    spl_acc,
+    wrapper,
+    tokenOwnerPublicKey,
+    src20_treasury,
+    src20_token,
+    src20ProgramId,
+    spl_treasury,
+    splTokenPublickey,
+    splProgramId,
+    spl_fee_vault,
+    programId,
+    payer,
+    connection
+  );
+  const { amount: fees } = await info(src20_fee_vault, connection);
+  if (BigInt(fees) == 0n) throw new Error('The src20 fee vault must hold fees to test DecommissionWrapper');
+  console.log('SRC20 fee vault data:', await info(src20_fee_vault, connection));
+  await decommission(
+    wrapper,
+    tokenOwnerPublicKey,
+    src20_treasury,
+    src20_fee_vault,
+    src20_token,
+    src20ProgramId,
+    spl_treasury,
+    spl_fee_vault,
+    spl_acc,
+    splProgramId,
+    programId,
+    payer,
+    connection
+  );
+  console.log('Wrapper data:', await info(wrapper, connection));
 }
 
 try { main() } catch (er) { console.error(er) }
@@ -29,6 +29,26 @@ pub enum AppError {
   Overflow,
   #[error("Wrapper unmatched")]
   UnmatchedWrapper,
+  #[error("Insufficient funds")]
+  InsufficientFunds,
+  #[error("Account frozen")]
+  FrozenAccount,
+  #[error("Amount below precision")]
+  Dust,
+  #[error("Invalid fee")]
+  InvalidFee,
+  #[error("Wrapper paused")]
+  Paused,
+  #[error("Wrapper decommissioned")]
+  Decommissioned,
+  #[error("Not a native wrapper")]
+  NotNative,
+  #[error("Under-collateralized")]
+  Undercollateralized,
+  #[error("Rate limit exceeded")]
+  RateLimited,
+  #[error("Fee above the accepted maximum")]
+  FeeExceeded,
 }
 
 impl From<AppError> for ProgramError {
@@ -57,6 +77,16 @@ impl PrintProgramError for AppError {
       AppError::NotInitialized => info!("Error: Not yet initialized"),
       AppError::Overflow => info!("Error: Operation overflowed"),
       AppError::UnmatchedWrapper => info!("Error: Wrapper unmatched"),
+      AppError::InsufficientFunds => info!("Error: Insufficient funds"),
+      AppError::FrozenAccount => info!("Error: Account frozen"),
+      AppError::Dust => info!("Error: Amount below precision"),
+      AppError::InvalidFee => info!("Error: Invalid fee"),
+      AppError::Paused => info!("Error: Wrapper paused"),
+      AppError::Decommissioned => info!("Error: Wrapper decommissioned"),
+      AppError::NotNative => info!("Error: Not a native wrapper"),
+      AppError::Undercollateralized => info!("Error: Under-collateralized"),
+      AppError::RateLimited => info!("Error: Rate limit exceeded"),
+      AppError::FeeExceeded => info!("Error: Fee above the accepted maximum"),
     }
   }
 }
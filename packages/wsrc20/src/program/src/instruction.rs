@@ -4,9 +4,57 @@ use std::{char, convert::TryInto};
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum AppInstruction {
-  Constructor { symbol: [char; 4] },
-  Wrap { amount: u64 },
-  Unwrap { amount: u64 },
+  Constructor {
+    symbol: [char; 4],
+    decimals: u8,
+  },
+  Wrap {
+    amount: u64,
+    max_fee_bps: u16,
+  },
+  Unwrap {
+    amount: u64,
+    max_fee_bps: u16,
+  },
+  SetFee {
+    fee_bps: u16,
+  },
+  WithdrawFee {
+    src20_amount: u64,
+    spl_amount: u64,
+  },
+  PauseWrapper {},
+  ResumeWrapper {},
+  DecommissionWrapper {},
+  WrapFromDelegate {
+    amount: u64,
+    max_fee_bps: u16,
+  },
+  WrapSol {
+    lamports: u64,
+    max_fee_bps: u16,
+  },
+  UnwrapToSol {
+    amount: u64,
+    max_fee_bps: u16,
+  },
+  ReverseConstructor {},
+  ReverseWrap {
+    amount: u64,
+  },
+  ReverseUnwrap {
+    amount: u64,
+  },
+  Bootstrap {
+    symbol: [char; 4],
+    decimals: u8,
+  },
+  ProveReserves {},
+  SetRateLimit {
+    window_duration: i64,
+    wrap_limit: u64,
+    unwrap_limit: u64,
+  },
 }
 impl AppInstruction {
   pub fn unpack(instruction: &[u8]) -> Result<Self, ProgramError> {
@@ -14,7 +62,7 @@ impl AppInstruction {
       .split_first()
       .ok_or(AppError::InvalidInstruction)?;
     Ok(match tag {
-      0 => {
+      0 | 15 => {
         let vec_symbol: Vec<_> = rest
           .get(..16)
           .unwrap()
@@ -23,22 +71,117 @@ impl AppInstruction {
           .map(|slice| u32::from_le_bytes(slice))
           .map(|slice| char::from_u32(slice).unwrap())
           .collect();
-        Self::Constructor {
-          symbol: [vec_symbol[0], vec_symbol[1], vec_symbol[2], vec_symbol[3]],
+        let decimals = rest
+          .get(16..17)
+          .and_then(|slice| slice.try_into().ok())
+          .map(u8::from_le_bytes)
+          .ok_or(AppError::InvalidInstruction)?;
+        let symbol = [vec_symbol[0], vec_symbol[1], vec_symbol[2], vec_symbol[3]];
+        match tag {
+          0 => Self::Constructor { symbol, decimals },
+          15 => Self::Bootstrap { symbol, decimals },
+          _ => unreachable!(),
+        }
+      }
+      1 | 2 | 9 | 10 | 11 => {
+        let amount = rest
+          .get(..8)
+          .and_then(|slice| slice.try_into().ok())
+          .map(u64::from_le_bytes)
+          .ok_or(AppError::InvalidInstruction)?;
+        // The highest fee the caller accepts, the fee may change before it lands
+        let max_fee_bps = rest
+          .get(8..10)
+          .and_then(|slice| slice.try_into().ok())
+          .map(u16::from_le_bytes)
+          .ok_or(AppError::InvalidInstruction)?;
+        match tag {
+          1 => Self::Wrap {
+            amount,
+            max_fee_bps,
+          },
+          2 => Self::Unwrap {
+            amount,
+            max_fee_bps,
+          },
+          9 => Self::WrapFromDelegate {
+            amount,
+            max_fee_bps,
+          },
+          10 => Self::WrapSol {
+            lamports: amount,
+            max_fee_bps,
+          },
+          11 => Self::UnwrapToSol {
+            amount,
+            max_fee_bps,
+          },
+          _ => unreachable!(),
+        }
+      }
+      4 => {
+        let fee_bps = rest
+          .get(..2)
+          .and_then(|slice| slice.try_into().ok())
+          .map(u16::from_le_bytes)
+          .ok_or(AppError::InvalidInstruction)?;
+        Self::SetFee { fee_bps }
+      }
+      5 => {
+        let src20_amount = rest
+          .get(..8)
+          .and_then(|slice| slice.try_into().ok())
+          .map(u64::from_le_bytes)
+          .ok_or(AppError::InvalidInstruction)?;
+        let spl_amount = rest
+          .get(8..16)
+          .and_then(|slice| slice.try_into().ok())
+          .map(u64::from_le_bytes)
+          .ok_or(AppError::InvalidInstruction)?;
+        Self::WithdrawFee {
+          src20_amount,
+          spl_amount,
         }
       }
-      1 | 2 => {
+      6 => Self::PauseWrapper {},
+      7 => Self::ResumeWrapper {},
+      8 => Self::DecommissionWrapper {},
+      12 => Self::ReverseConstructor {},
+      13 | 14 => {
         let amount = rest
           .get(..8)
           .and_then(|slice| slice.try_into().ok())
           .map(u64::from_le_bytes)
           .ok_or(AppError::InvalidInstruction)?;
         match tag {
-          1 => Self::Wrap { amount },
-          2 => Self::Unwrap { amount },
+          13 => Self::ReverseWrap { amount },
+          14 => Self::ReverseUnwrap { amount },
           _ => unreachable!(),
         }
       }
+      16 => Self::ProveReserves {},
+      17 => {
+        let window_duration = rest
+          .get(..8)
+          .and_then(|slice| slice.try_into().ok())
+          .map(i64::from_le_bytes)
+          .ok_or(AppError::InvalidInstruction)?;
+        let wrap_limit = rest
+          .get(8..16)
+          .and_then(|slice| slice.try_into().ok())
+          .map(u64::from_le_bytes)
+          .ok_or(AppError::InvalidInstruction)?;
+        let unwrap_limit = rest
+          .get(16..24)
+          .and_then(|slice| slice.try_into().ok())
+          .map(u64::from_le_bytes)
+          .ok_or(AppError::InvalidInstruction)?;
+        Self::SetRateLimit {
+          window_duration,
+          wrap_limit,
+          unwrap_limit,
+        }
+      }
       _ => return Err(AppError::InvalidInstruction.into()),
     })
   }
@@ -1,13 +1,73 @@
 use solana_program::{
+  declare_id,
   instruction::{AccountMeta, Instruction},
   program_error::ProgramError,
   pubkey::Pubkey,
 };
 use std::mem::size_of;
 
+// The official SPL token program
+declare_id!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+/// The mint of wrapped SOL
+pub mod native_mint {
+  solana_program::declare_id!("So11111111111111111111111111111111111111112");
+}
+
+/// Kinds of authority that SetAuthority can change
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AuthorityType {
+  MintTokens,
+  FreezeAccount,
+  AccountOwner,
+  CloseAccount,
+}
+
 pub struct ISPL {}
 
 impl ISPL {
+  /// The owner signs alone, or is a multisig account signed by the given signers
+  fn push_owner(accounts: &mut Vec<AccountMeta>, owner: Pubkey, signers: &[Pubkey]) {
+    accounts.push(AccountMeta::new_readonly(owner, signers.is_empty()));
+    for signer in signers {
+      accounts.push(AccountMeta::new_readonly(*signer, true));
+    }
+  }
+  fn push_pubkey_option(data: &mut Vec<u8>, key: Option<Pubkey>) {
+    match key {
+      Some(key) => {
+        data.push(1);
+        data.extend_from_slice(key.as_ref());
+      }
+      None => data.push(0),
+    }
+  }
+  pub fn initialize_mint(
+    program_id: Pubkey,
+    mint_acc: Pubkey,
+    mint_authority: Pubkey,
+    freeze_authority: Option<Pubkey>,
+    decimals: u8,
+    sysvar_rent_acc: Pubkey,
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // InitializeMint - Code 0
+    data.push(0);
+    data.push(decimals);
+    data.extend_from_slice(mint_authority.as_ref());
+    Self::push_pubkey_option(&mut data, freeze_authority);
+    // Build accounts
+    let mut accounts = Vec::with_capacity(2);
+    accounts.push(AccountMeta::new(mint_acc, false));
+    accounts.push(AccountMeta::new_readonly(sysvar_rent_acc, false));
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
   pub fn initialize_account(
     program_id: Pubkey,
     owner: Pubkey,
@@ -35,6 +95,7 @@ impl ISPL {
   pub fn transfer(
     program_id: Pubkey,
     owner: Pubkey,
+    signers: &[Pubkey],
     src_acc: Pubkey,
     dst_acc: Pubkey,
     amount: u64,
@@ -45,10 +106,246 @@ impl ISPL {
     data.push(3);
     data.extend_from_slice(&amount.to_le_bytes());
     // Build accounts
-    let mut accounts = Vec::with_capacity(3);
+    let mut accounts = Vec::with_capacity(3 + signers.len());
+    accounts.push(AccountMeta::new(src_acc, false));
+    accounts.push(AccountMeta::new(dst_acc, false));
+    Self::push_owner(&mut accounts, owner, signers);
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+  pub fn approve(
+    program_id: Pubkey,
+    owner: Pubkey,
+    signers: &[Pubkey],
+    src_acc: Pubkey,
+    delegate: Pubkey,
+    amount: u64,
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // Approve - Code 4
+    data.push(4);
+    data.extend_from_slice(&amount.to_le_bytes());
+    // Build accounts
+    let mut accounts = Vec::with_capacity(3 + signers.len());
+    accounts.push(AccountMeta::new(src_acc, false));
+    accounts.push(AccountMeta::new_readonly(delegate, false));
+    Self::push_owner(&mut accounts, owner, signers);
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+  pub fn revoke(
+    program_id: Pubkey,
+    owner: Pubkey,
+    signers: &[Pubkey],
+    src_acc: Pubkey,
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // Revoke - Code 5
+    data.push(5);
+    // Build accounts
+    let mut accounts = Vec::with_capacity(2 + signers.len());
+    accounts.push(AccountMeta::new(src_acc, false));
+    Self::push_owner(&mut accounts, owner, signers);
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+  pub fn set_authority(
+    program_id: Pubkey,
+    owner: Pubkey,
+    signers: &[Pubkey],
+    target_acc: Pubkey,
+    authority_type: AuthorityType,
+    new_authority: Option<Pubkey>,
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // SetAuthority - Code 6
+    data.push(6);
+    data.push(authority_type as u8);
+    Self::push_pubkey_option(&mut data, new_authority);
+    // Build accounts
+    let mut accounts = Vec::with_capacity(2 + signers.len());
+    accounts.push(AccountMeta::new(target_acc, false));
+    Self::push_owner(&mut accounts, owner, signers);
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+  pub fn mint_to(
+    program_id: Pubkey,
+    mint_authority: Pubkey,
+    signers: &[Pubkey],
+    mint_acc: Pubkey,
+    dst_acc: Pubkey,
+    amount: u64,
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // MintTo - Code 7
+    data.push(7);
+    data.extend_from_slice(&amount.to_le_bytes());
+    // Build accounts
+    let mut accounts = Vec::with_capacity(3 + signers.len());
+    accounts.push(AccountMeta::new(mint_acc, false));
+    accounts.push(AccountMeta::new(dst_acc, false));
+    Self::push_owner(&mut accounts, mint_authority, signers);
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+  pub fn burn(
+    program_id: Pubkey,
+    owner: Pubkey,
+    signers: &[Pubkey],
+    src_acc: Pubkey,
+    mint_acc: Pubkey,
+    amount: u64,
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // Burn - Code 8
+    data.push(8);
+    data.extend_from_slice(&amount.to_le_bytes());
+    // Build accounts
+    let mut accounts = Vec::with_capacity(3 + signers.len());
+    accounts.push(AccountMeta::new(src_acc, false));
+    accounts.push(AccountMeta::new(mint_acc, false));
+    Self::push_owner(&mut accounts, owner, signers);
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+  pub fn close_account(
+    program_id: Pubkey,
+    owner: Pubkey,
+    signers: &[Pubkey],
+    target_acc: Pubkey,
+    dst_acc: Pubkey,
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // CloseAccount - Code 9
+    data.push(9);
+    // Build accounts
+    let mut accounts = Vec::with_capacity(3 + signers.len());
+    accounts.push(AccountMeta::new(target_acc, false));
+    accounts.push(AccountMeta::new(dst_acc, false));
+    Self::push_owner(&mut accounts, owner, signers);
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+  pub fn freeze_account(
+    program_id: Pubkey,
+    freeze_authority: Pubkey,
+    signers: &[Pubkey],
+    target_acc: Pubkey,
+    mint_acc: Pubkey,
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // FreezeAccount - Code 10
+    data.push(10);
+    // Build accounts
+    let mut accounts = Vec::with_capacity(3 + signers.len());
+    accounts.push(AccountMeta::new(target_acc, false));
+    accounts.push(AccountMeta::new_readonly(mint_acc, false));
+    Self::push_owner(&mut accounts, freeze_authority, signers);
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+  pub fn thaw_account(
+    program_id: Pubkey,
+    freeze_authority: Pubkey,
+    signers: &[Pubkey],
+    target_acc: Pubkey,
+    mint_acc: Pubkey,
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // ThawAccount - Code 11
+    data.push(11);
+    // Build accounts
+    let mut accounts = Vec::with_capacity(3 + signers.len());
+    accounts.push(AccountMeta::new(target_acc, false));
+    accounts.push(AccountMeta::new_readonly(mint_acc, false));
+    Self::push_owner(&mut accounts, freeze_authority, signers);
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+  // Mirrors the SPL TransferChecked accounts and data one to one, a struct would only hide them
+  #[allow(clippy::too_many_arguments)]
+  pub fn transfer_checked(
+    program_id: Pubkey,
+    owner: Pubkey,
+    signers: &[Pubkey],
+    src_acc: Pubkey,
+    mint_acc: Pubkey,
+    dst_acc: Pubkey,
+    amount: u64,
+    decimals: u8,
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // TransferChecked - Code 12
+    data.push(12);
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.push(decimals);
+    // Build accounts
+    let mut accounts = Vec::with_capacity(4 + signers.len());
     accounts.push(AccountMeta::new(src_acc, false));
+    accounts.push(AccountMeta::new_readonly(mint_acc, false));
     accounts.push(AccountMeta::new(dst_acc, false));
-    accounts.push(AccountMeta::new_readonly(owner, true));
+    Self::push_owner(&mut accounts, owner, signers);
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+  pub fn sync_native(program_id: Pubkey, target_acc: Pubkey) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // SyncNative - Code 17
+    data.push(17);
+    // Build accounts
+    let mut accounts = Vec::with_capacity(1);
+    accounts.push(AccountMeta::new(target_acc, false));
     // Return
     Ok(Instruction {
       program_id,
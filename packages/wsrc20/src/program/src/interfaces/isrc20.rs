@@ -258,7 +258,7 @@ impl ISRC20 {
     // Build accounts
     let mut accounts = Vec::with_capacity(3);
     accounts.push(AccountMeta::new_readonly(owner, true));
-    accounts.push(AccountMeta::new_readonly(token_acc, false));
+    accounts.push(AccountMeta::new(token_acc, false));
     accounts.push(AccountMeta::new(target_acc, false));
     // Return
     Ok(Instruction {
@@ -267,4 +267,77 @@ impl ISRC20 {
       data,
     })
   }
+
+  pub fn audit_supply(
+    program_id: Pubkey,
+    token_acc: Pubkey,
+    holder_accs: &[Pubkey],
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // AuditSupply - Code 12
+    data.push(12);
+    // Build accounts
+    let mut accounts = Vec::with_capacity(1 + holder_accs.len());
+    accounts.push(AccountMeta::new_readonly(token_acc, false));
+    for holder_acc in holder_accs {
+      accounts.push(AccountMeta::new_readonly(*holder_acc, false));
+    }
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+
+  pub fn mint_to(
+    program_id: Pubkey,
+    authority: Pubkey,
+    token_acc: Pubkey,
+    dst_acc: Pubkey,
+    amount: u64,
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // MintTo - Code 19
+    data.push(19);
+    data.extend_from_slice(&amount.to_le_bytes());
+    // Build accounts
+    let mut accounts = Vec::with_capacity(3);
+    accounts.push(AccountMeta::new_readonly(authority, true));
+    accounts.push(AccountMeta::new(token_acc, false));
+    accounts.push(AccountMeta::new(dst_acc, false));
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
+
+  pub fn burn(
+    program_id: Pubkey,
+    owner: Pubkey,
+    token_acc: Pubkey,
+    src_acc: Pubkey,
+    amount: u64,
+  ) -> Result<Instruction, ProgramError> {
+    // Build data
+    let mut data = Vec::with_capacity(size_of::<Self>());
+    // Burn - Code 20
+    data.push(20);
+    data.extend_from_slice(&amount.to_le_bytes());
+    // Build accounts
+    let mut accounts = Vec::with_capacity(3);
+    accounts.push(AccountMeta::new_readonly(owner, true));
+    accounts.push(AccountMeta::new(token_acc, false));
+    accounts.push(AccountMeta::new(src_acc, false));
+    // Return
+    Ok(Instruction {
+      program_id,
+      accounts,
+      data,
+    })
+  }
 }
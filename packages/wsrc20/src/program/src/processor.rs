@@ -1,16 +1,38 @@
 use crate::error::AppError;
 use crate::instruction::AppInstruction;
-use crate::interfaces::{ispl::ISPL, isrc20::ISRC20};
-use crate::schema::{mint::Mint, wrapper::Wrapper};
+use crate::interfaces::{
+  ispl::{self, native_mint, ISPL},
+  isrc20::ISRC20,
+};
+use crate::schema::{
+  account::Account,
+  mint::Mint,
+  registry::Registry,
+  reverse_wrapper::ReverseWrapper,
+  src20_account::Account as Src20Account,
+  token::Token,
+  wrapper::{Wrapper, DESTINATION_SEED, MAX_FEE_BPS},
+};
 use solana_program::{
   account_info::{next_account_info, AccountInfo},
   entrypoint::ProgramResult,
   info,
   program::{invoke, invoke_signed},
+  program_option::COption,
   program_pack::{IsInitialized, Pack},
   pubkey::Pubkey,
+  rent::Rent,
+  system_instruction, system_program,
+  sysvar::{clock::Clock, Sysvar},
 };
 
+/// Seeds of the accounts created by the bootstrap, next to the wrapper key
+pub const BOOTSTRAP_SRC20_TOKEN: &[u8] = b"src20_token";
+pub const BOOTSTRAP_SRC20_TREASURY: &[u8] = b"src20_treasury";
+pub const BOOTSTRAP_SRC20_FEE_VAULT: &[u8] = b"src20_fee_vault";
+pub const BOOTSTRAP_SPL_TREASURY: &[u8] = b"spl_treasury";
+pub const BOOTSTRAP_SPL_FEE_VAULT: &[u8] = b"spl_fee_vault";
+
 pub struct Processor {}
 
 impl Processor {
@@ -21,9 +43,322 @@ impl Processor {
   ) -> ProgramResult {
     let instruction = AppInstruction::unpack(instruction_data)?;
     match instruction {
-      AppInstruction::Constructor { symbol } => {
+      AppInstruction::Constructor { symbol, decimals } => {
         info!("Calling Constructor function");
+        Self::construct(program_id, accounts, symbol, decimals, &[])
+      }
+
+      AppInstruction::Bootstrap { symbol, decimals } => {
+        info!("Calling Bootstrap function");
+        // Same accounts as Constructor, those created below must not exist yet
+        let accounts_iter = &mut accounts.iter();
+        let wrapper_acc = next_account_info(accounts_iter)?;
+        let _token_owner_acc = next_account_info(accounts_iter)?;
+        let src20_treasury_acc = next_account_info(accounts_iter)?;
+        let src20_token_acc = next_account_info(accounts_iter)?;
+        let src20_token_program = next_account_info(accounts_iter)?;
+        let spl_treasury_acc = next_account_info(accounts_iter)?;
+        let _spl_token_acc = next_account_info(accounts_iter)?;
+        let spl_token_program = next_account_info(accounts_iter)?;
+        let sysvar_rent_acc = next_account_info(accounts_iter)?;
+        let _fee_authority = next_account_info(accounts_iter)?;
+        let src20_fee_vault_acc = next_account_info(accounts_iter)?;
+        let spl_fee_vault_acc = next_account_info(accounts_iter)?;
+        let payer = next_account_info(accounts_iter)?;
+        let _registry_acc = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        if *system_program.key != system_program::id() {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+        if !payer.is_signer || !wrapper_acc.is_signer {
+          return Err(AppError::InvalidOwner.into());
+        }
+
+        // Accounts are topped up rather than created, lamports sent beforehand cannot block them
+        let rent = Rent::from_account_info(sysvar_rent_acc)?;
+        Self::create_derived_account(
+          payer,
+          wrapper_acc,
+          system_program,
+          &rent,
+          Wrapper::LEN,
+          program_id,
+          &[],
+        )?;
+
+        // Every other account lives at an address derived from the wrapper
+        let wrapper_key_bytes = wrapper_acc.key.to_bytes();
+        let derived = [
+          (
+            src20_token_acc,
+            BOOTSTRAP_SRC20_TOKEN,
+            Token::LEN,
+            src20_token_program.key,
+          ),
+          (
+            src20_treasury_acc,
+            BOOTSTRAP_SRC20_TREASURY,
+            Src20Account::LEN,
+            src20_token_program.key,
+          ),
+          (
+            src20_fee_vault_acc,
+            BOOTSTRAP_SRC20_FEE_VAULT,
+            Src20Account::LEN,
+            src20_token_program.key,
+          ),
+          (
+            spl_treasury_acc,
+            BOOTSTRAP_SPL_TREASURY,
+            Account::LEN,
+            spl_token_program.key,
+          ),
+          (
+            spl_fee_vault_acc,
+            BOOTSTRAP_SPL_FEE_VAULT,
+            Account::LEN,
+            spl_token_program.key,
+          ),
+        ];
+        let bumps: Vec<[u8; 1]> = derived
+          .iter()
+          .map(|(_, tag, _, _)| {
+            [Pubkey::find_program_address(&[&wrapper_key_bytes[..], tag], program_id).1]
+          })
+          .collect();
+        let signer_seeds: Vec<[&[u8]; 3]> = derived
+          .iter()
+          .zip(bumps.iter())
+          .map(|((_, tag, _, _), bump)| [&wrapper_key_bytes[..], *tag, &bump[..]])
+          .collect();
+        for ((acc, _, space, owner), seeds) in derived.iter().zip(signer_seeds.iter()) {
+          if Pubkey::create_program_address(seeds, program_id)? != *acc.key {
+            return Err(AppError::UnmatchedWrapper.into());
+          }
+          Self::create_derived_account(
+            payer,
+            acc,
+            system_program,
+            &rent,
+            *space,
+            owner,
+            &[&seeds[..]],
+          )?;
+        }
+
+        let signer_seeds: Vec<&[&[u8]]> = signer_seeds.iter().map(|seeds| &seeds[..]).collect();
+        Self::construct(program_id, accounts, symbol, decimals, &signer_seeds)
+      }
+
+      AppInstruction::Wrap {
+        amount,
+        max_fee_bps,
+      }
+      | AppInstruction::WrapFromDelegate {
+        amount,
+        max_fee_bps,
+      } => {
+        info!("Calling Wrap/WrapFromDelegate function");
+        // From a delegate, the owner is a relayer and the wrapper authority pulls the funds
+        let from_delegate = instruction
+          == AppInstruction::WrapFromDelegate {
+            amount,
+            max_fee_bps,
+          };
+        let accounts_iter = &mut accounts.iter();
+        let owner = next_account_info(accounts_iter)?;
+        let src_acc = next_account_info(accounts_iter)?;
+        let dst_acc = next_account_info(accounts_iter)?;
+        let wrapper_acc = next_account_info(accounts_iter)?;
+        let token_owner_acc = next_account_info(accounts_iter)?; // For both types
+        let src20_treasury_acc = next_account_info(accounts_iter)?;
+        let src20_token_acc = next_account_info(accounts_iter)?;
+        let src20_token_program = next_account_info(accounts_iter)?;
+        let spl_treasury_acc = next_account_info(accounts_iter)?;
+        let spl_token_acc = next_account_info(accounts_iter)?;
+        let spl_token_program = next_account_info(accounts_iter)?;
+        let fee_vault_acc = next_account_info(accounts_iter)?;
+        let sysvar_clock_acc = next_account_info(accounts_iter)?;
+        if wrapper_acc.owner != program_id {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+
+        let mut wrapper_data = Wrapper::unpack(&wrapper_acc.data.borrow())?;
+        let seed: &[&[_]] = &[
+          &wrapper_acc.key.to_bytes()[..],
+          &wrapper_data.authority_bump()[..],
+        ];
+        let token_owner_key = Pubkey::create_program_address(&seed, program_id)?;
+        if !owner.is_signer || token_owner_key != *token_owner_acc.key {
+          return Err(AppError::InvalidOwner.into());
+        }
+        if wrapper_data.paused {
+          return Err(AppError::Paused.into());
+        }
+        wrapper_data.check_fee(max_fee_bps)?;
+        if wrapper_data.src20_treasury != *src20_treasury_acc.key
+          || wrapper_data.src20_token != *src20_token_acc.key
+          || wrapper_data.spl_treasury != *spl_treasury_acc.key
+          || wrapper_data.spl_token != *spl_token_acc.key
+          || wrapper_data.src20_fee_vault != *fee_vault_acc.key
+        {
+          return Err(AppError::UnmatchedWrapper.into());
+        }
+        // CPI targets must be the programs recorded at construction
+        let (src20_program, spl_program) =
+          wrapper_data.programs(src20_token_acc.owner, spl_token_acc.owner);
+        if src20_program != *src20_token_program.key || spl_program != *spl_token_program.key {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+        // The amount must be representable on the src20 side, dust is refused
+        let spl_amount = amount;
+        let src20_amount = wrapper_data.spl_to_src20(spl_amount)?;
+        // Count against the rate limit of the current window
+        let clock = Clock::from_account_info(sysvar_clock_acc)?;
+        wrapper_data.consume_limit(clock.unix_timestamp, true, spl_amount)?;
+        Wrapper::pack(wrapper_data, &mut wrapper_acc.data.borrow_mut())?;
+        // Pre-check the SPL source and the src20 destination, a missing one is opened below
+        let dst_missing = !from_delegate && *dst_acc.owner == system_program::id();
+        if src_acc.owner != spl_token_program.key
+          || (!dst_missing && dst_acc.owner != src20_token_program.key)
+        {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+        let src_data = Account::unpack(&src_acc.data.borrow())?;
+        if src_data.mint != wrapper_data.spl_token {
+          return Err(AppError::IncorrectTokenId.into());
+        }
+        if src_data.is_frozen() {
+          return Err(AppError::FrozenAccount.into());
+        }
+        if from_delegate {
+          if src_data.delegate != COption::Some(token_owner_key) {
+            return Err(AppError::InvalidOwner.into());
+          }
+          if src_data.delegated_amount < spl_amount {
+            return Err(AppError::InsufficientFunds.into());
+          }
+          // The relayer cannot redirect the wrapped funds
+          let dst_data = Src20Account::unpack(&dst_acc.data.borrow())?;
+          if dst_data.owner != src_data.owner {
+            return Err(AppError::InvalidOwner.into());
+          }
+        } else if src_data.owner != *owner.key {
+          return Err(AppError::InvalidOwner.into());
+        }
+        if src_data.amount < spl_amount {
+          return Err(AppError::InsufficientFunds.into());
+        }
+        // Open a missing destination at the address derived for the owner, the wrapper
+        // signs for it so the caller needs neither a prepared account nor an extra signer
+        if dst_missing {
+          let system_program = next_account_info(accounts_iter)?;
+          let sysvar_rent_acc = next_account_info(accounts_iter)?;
+          let (dst_key, dst_bump) =
+            Wrapper::find_destination(owner.key, src20_token_acc.key, program_id);
+          if dst_key != *dst_acc.key {
+            return Err(AppError::InvalidOwner.into());
+          }
+          let dst_seed: &[&[_]] = &[
+            DESTINATION_SEED,
+            &owner.key.to_bytes()[..],
+            &src20_token_acc.key.to_bytes()[..],
+            &[dst_bump],
+          ];
+          let rent = Rent::from_account_info(sysvar_rent_acc)?;
+          Self::create_derived_account(
+            owner,
+            dst_acc,
+            system_program,
+            &rent,
+            Src20Account::LEN,
+            src20_token_program.key,
+            &[dst_seed],
+          )?;
+          let account_constructor_ix = ISRC20::account_constructor(
+            *src20_token_program.key,
+            *owner.key,
+            *src20_token_acc.key,
+            *dst_acc.key,
+          )?;
+          invoke_signed(
+            &account_constructor_ix,
+            &[
+              src20_token_program.clone(),
+              owner.clone(),
+              src20_token_acc.clone(),
+              dst_acc.clone(),
+            ],
+            &[dst_seed],
+          )?;
+        }
+
+        if from_delegate {
+          let in_ix = ISPL::transfer(
+            *spl_token_program.key,
+            *token_owner_acc.key,
+            &[],
+            *src_acc.key,
+            *spl_treasury_acc.key,
+            spl_amount,
+          )?;
+          invoke_signed(
+            &in_ix,
+            &[
+              spl_token_program.clone(),
+              token_owner_acc.clone(),
+              src_acc.clone(),
+              spl_treasury_acc.clone(),
+            ],
+            &[&seed],
+          )?;
+        } else {
+          let in_ix = ISPL::transfer(
+            *spl_token_program.key,
+            *owner.key,
+            &[],
+            *src_acc.key,
+            *spl_treasury_acc.key,
+            spl_amount,
+          )?;
+          invoke(
+            &in_ix,
+            &[
+              spl_token_program.clone(),
+              owner.clone(),
+              src_acc.clone(),
+              spl_treasury_acc.clone(),
+            ],
+          )?;
+        }
+
+        // The src20 is minted against the deposit, the fee into the fee vault
+        let fee = wrapper_data.fee(src20_amount).ok_or(AppError::Overflow)?;
+        for (acc, amount) in [(dst_acc, src20_amount - fee), (fee_vault_acc, fee)].iter() {
+          Self::issue_src20(
+            &wrapper_data,
+            token_owner_acc,
+            src20_treasury_acc,
+            src20_token_acc,
+            src20_token_program,
+            acc,
+            *amount,
+            seed,
+          )?;
+        }
+
+        Ok(())
+      }
+
+      AppInstruction::Unwrap {
+        amount,
+        max_fee_bps,
+      } => {
+        info!("Calling Unwrap function");
         let accounts_iter = &mut accounts.iter();
+        let owner = next_account_info(accounts_iter)?;
+        let src_acc = next_account_info(accounts_iter)?;
+        let dst_acc = next_account_info(accounts_iter)?;
         let wrapper_acc = next_account_info(accounts_iter)?;
         let token_owner_acc = next_account_info(accounts_iter)?; // For both types
         let src20_treasury_acc = next_account_info(accounts_iter)?;
@@ -32,33 +367,698 @@ impl Processor {
         let spl_treasury_acc = next_account_info(accounts_iter)?;
         let spl_token_acc = next_account_info(accounts_iter)?;
         let spl_token_program = next_account_info(accounts_iter)?;
+        let fee_vault_acc = next_account_info(accounts_iter)?;
+        let sysvar_clock_acc = next_account_info(accounts_iter)?;
+        if wrapper_acc.owner != program_id {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+
+        let mut wrapper_data = Wrapper::unpack(&wrapper_acc.data.borrow())?;
+        let seed: &[&[_]] = &[
+          &wrapper_acc.key.to_bytes()[..],
+          &wrapper_data.authority_bump()[..],
+        ];
+        let token_owner_key = Pubkey::create_program_address(&seed, program_id)?;
+        if !owner.is_signer || token_owner_key != *token_owner_acc.key {
+          return Err(AppError::InvalidOwner.into());
+        }
+        if wrapper_data.paused {
+          return Err(AppError::Paused.into());
+        }
+        wrapper_data.check_fee(max_fee_bps)?;
+        if wrapper_data.src20_treasury != *src20_treasury_acc.key
+          || wrapper_data.src20_token != *src20_token_acc.key
+          || wrapper_data.spl_treasury != *spl_treasury_acc.key
+          || wrapper_data.spl_token != *spl_token_acc.key
+          || wrapper_data.spl_fee_vault != *fee_vault_acc.key
+        {
+          return Err(AppError::UnmatchedWrapper.into());
+        }
+        // CPI targets must be the programs recorded at construction
+        let (src20_program, spl_program) =
+          wrapper_data.programs(src20_token_acc.owner, spl_token_acc.owner);
+        if src20_program != *src20_token_program.key || spl_program != *spl_token_program.key {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+        // The amount must be representable on the SPL side, dust is refused
+        let src20_amount = amount;
+        let spl_amount = wrapper_data.src20_to_spl(src20_amount)?;
+        // Count against the rate limit of the current window
+        let clock = Clock::from_account_info(sysvar_clock_acc)?;
+        wrapper_data.consume_limit(clock.unix_timestamp, false, spl_amount)?;
+        Wrapper::pack(wrapper_data, &mut wrapper_acc.data.borrow_mut())?;
+        // Pre-check the src20 source and the SPL destination
+        if src_acc.owner != src20_token_program.key || dst_acc.owner != spl_token_program.key {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+        let dst_data = Account::unpack(&dst_acc.data.borrow())?;
+        if dst_data.mint != wrapper_data.spl_token {
+          return Err(AppError::IncorrectTokenId.into());
+        }
+        if dst_data.is_frozen() {
+          return Err(AppError::FrozenAccount.into());
+        }
+        let spl_treasury_data = Account::unpack(&spl_treasury_acc.data.borrow())?;
+        if spl_treasury_data.amount < spl_amount {
+          return Err(AppError::InsufficientFunds.into());
+        }
+
+        // The src20 is burnt, the supply follows what is left wrapped
+        Self::retire_src20(
+          &wrapper_data,
+          owner,
+          src20_treasury_acc,
+          src20_token_acc,
+          src20_token_program,
+          src_acc,
+          src20_amount,
+        )?;
+
+        // The fee is taken in SPL and kept in the fee vault
+        let fee = wrapper_data.fee(spl_amount).ok_or(AppError::Overflow)?;
+        let out_ix = ISPL::transfer(
+          *spl_token_program.key,
+          *token_owner_acc.key,
+          &[],
+          *spl_treasury_acc.key,
+          *dst_acc.key,
+          spl_amount - fee,
+        )?;
+        invoke_signed(
+          &out_ix,
+          &[
+            spl_token_program.clone(),
+            token_owner_acc.clone(),
+            spl_treasury_acc.clone(),
+            dst_acc.clone(),
+          ],
+          &[&seed],
+        )?;
+        if fee > 0 {
+          let fee_ix = ISPL::transfer(
+            *spl_token_program.key,
+            *token_owner_acc.key,
+            &[],
+            *spl_treasury_acc.key,
+            *fee_vault_acc.key,
+            fee,
+          )?;
+          invoke_signed(
+            &fee_ix,
+            &[
+              spl_token_program.clone(),
+              token_owner_acc.clone(),
+              spl_treasury_acc.clone(),
+              fee_vault_acc.clone(),
+            ],
+            &[&seed],
+          )?;
+        }
+
+        Ok(())
+      }
+
+      AppInstruction::SetFee { fee_bps } => {
+        info!("Calling SetFee function");
+        let accounts_iter = &mut accounts.iter();
+        let fee_authority = next_account_info(accounts_iter)?;
+        let wrapper_acc = next_account_info(accounts_iter)?;
+        if wrapper_acc.owner != program_id {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+
+        let mut wrapper_data = Wrapper::unpack(&wrapper_acc.data.borrow())?;
+        if !fee_authority.is_signer || wrapper_data.fee_authority != *fee_authority.key {
+          return Err(AppError::InvalidOwner.into());
+        }
+        if fee_bps > MAX_FEE_BPS {
+          return Err(AppError::InvalidFee.into());
+        }
+        wrapper_data.fee_bps = fee_bps;
+        Wrapper::pack(wrapper_data, &mut wrapper_acc.data.borrow_mut())?;
+
+        Ok(())
+      }
+
+      AppInstruction::WithdrawFee {
+        src20_amount,
+        spl_amount,
+      } => {
+        info!("Calling WithdrawFee function");
+        let accounts_iter = &mut accounts.iter();
+        let fee_authority = next_account_info(accounts_iter)?;
+        let wrapper_acc = next_account_info(accounts_iter)?;
+        let token_owner_acc = next_account_info(accounts_iter)?; // For both types
+        let src20_fee_vault_acc = next_account_info(accounts_iter)?;
+        let src20_dst_acc = next_account_info(accounts_iter)?;
+        let src20_token_acc = next_account_info(accounts_iter)?;
+        let src20_token_program = next_account_info(accounts_iter)?;
+        let spl_fee_vault_acc = next_account_info(accounts_iter)?;
+        let spl_dst_acc = next_account_info(accounts_iter)?;
+        let spl_token_program = next_account_info(accounts_iter)?;
+        if wrapper_acc.owner != program_id {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+
+        let wrapper_data = Wrapper::unpack(&wrapper_acc.data.borrow())?;
+        let seed: &[&[_]] = &[
+          &wrapper_acc.key.to_bytes()[..],
+          &wrapper_data.authority_bump()[..],
+        ];
+        let token_owner_key = Pubkey::create_program_address(&seed, program_id)?;
+        if !fee_authority.is_signer
+          || wrapper_data.fee_authority != *fee_authority.key
+          || token_owner_key != *token_owner_acc.key
+        {
+          return Err(AppError::InvalidOwner.into());
+        }
+        if wrapper_data.src20_fee_vault != *src20_fee_vault_acc.key
+          || wrapper_data.src20_token != *src20_token_acc.key
+          || wrapper_data.spl_fee_vault != *spl_fee_vault_acc.key
+        {
+          return Err(AppError::UnmatchedWrapper.into());
+        }
+        if wrapper_data.src20_program != *src20_token_program.key
+          || wrapper_data.spl_program != *spl_token_program.key
+        {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+
+        if src20_amount > 0 {
+          let src20_ix = ISRC20::transfer(
+            *src20_token_program.key,
+            *token_owner_acc.key,
+            *src20_token_acc.key,
+            *src20_fee_vault_acc.key,
+            *src20_dst_acc.key,
+            src20_amount,
+          )?;
+          invoke_signed(
+            &src20_ix,
+            &[
+              src20_token_program.clone(),
+              token_owner_acc.clone(),
+              src20_token_acc.clone(),
+              src20_fee_vault_acc.clone(),
+              src20_dst_acc.clone(),
+            ],
+            &[&seed],
+          )?;
+        }
+        if spl_amount > 0 {
+          let spl_ix = ISPL::transfer(
+            *spl_token_program.key,
+            *token_owner_acc.key,
+            &[],
+            *spl_fee_vault_acc.key,
+            *spl_dst_acc.key,
+            spl_amount,
+          )?;
+          invoke_signed(
+            &spl_ix,
+            &[
+              spl_token_program.clone(),
+              token_owner_acc.clone(),
+              spl_fee_vault_acc.clone(),
+              spl_dst_acc.clone(),
+            ],
+            &[&seed],
+          )?;
+        }
+
+        Ok(())
+      }
+
+      AppInstruction::PauseWrapper {} | AppInstruction::ResumeWrapper {} => {
+        info!("Calling PauseWrapper/ResumeWrapper function");
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let wrapper_acc = next_account_info(accounts_iter)?;
+        if wrapper_acc.owner != program_id {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+
+        let mut wrapper_data = Wrapper::unpack(&wrapper_acc.data.borrow())?;
+        if !authority.is_signer || wrapper_data.authority != *authority.key {
+          return Err(AppError::InvalidOwner.into());
+        }
+        if wrapper_data.decommissioned {
+          return Err(AppError::Decommissioned.into());
+        }
+        wrapper_data.paused = instruction == AppInstruction::PauseWrapper {};
+        Wrapper::pack(wrapper_data, &mut wrapper_acc.data.borrow_mut())?;
+
+        Ok(())
+      }
+
+      AppInstruction::DecommissionWrapper {} => {
+        info!("Calling DecommissionWrapper function");
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let wrapper_acc = next_account_info(accounts_iter)?;
+        let token_owner_acc = next_account_info(accounts_iter)?; // For both types
+        let src20_treasury_acc = next_account_info(accounts_iter)?;
+        let src20_fee_vault_acc = next_account_info(accounts_iter)?;
+        let src20_token_acc = next_account_info(accounts_iter)?;
+        let src20_token_program = next_account_info(accounts_iter)?;
+        let spl_treasury_acc = next_account_info(accounts_iter)?;
+        let spl_fee_vault_acc = next_account_info(accounts_iter)?;
+        let spl_dst_acc = next_account_info(accounts_iter)?;
+        let spl_token_program = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        if wrapper_acc.owner != program_id || *system_program.key != system_program::id() {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+
+        let mut wrapper_data = Wrapper::unpack(&wrapper_acc.data.borrow())?;
+        let seed: &[&[_]] = &[
+          &wrapper_acc.key.to_bytes()[..],
+          &wrapper_data.authority_bump()[..],
+        ];
+        let token_owner_key = Pubkey::create_program_address(&seed, program_id)?;
+        if !authority.is_signer
+          || wrapper_data.authority != *authority.key
+          || token_owner_key != *token_owner_acc.key
+        {
+          return Err(AppError::InvalidOwner.into());
+        }
+        if wrapper_data.decommissioned {
+          return Err(AppError::Decommissioned.into());
+        }
+        if wrapper_data.src20_treasury != *src20_treasury_acc.key
+          || wrapper_data.src20_fee_vault != *src20_fee_vault_acc.key
+          || wrapper_data.src20_token != *src20_token_acc.key
+          || wrapper_data.spl_treasury != *spl_treasury_acc.key
+          || wrapper_data.spl_fee_vault != *spl_fee_vault_acc.key
+        {
+          return Err(AppError::UnmatchedWrapper.into());
+        }
+        if wrapper_data.src20_program != *src20_token_program.key
+          || wrapper_data.spl_program != *spl_token_program.key
+        {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+
+        // Nothing may be left in circulation, the collected fees are held by the wrapper
+        let audit_supply_ix = ISRC20::audit_supply(
+          *src20_token_program.key,
+          *src20_token_acc.key,
+          &[*src20_treasury_acc.key, *src20_fee_vault_acc.key],
+        )?;
+        invoke(
+          &audit_supply_ix,
+          &[
+            src20_token_program.clone(),
+            src20_token_acc.clone(),
+            src20_treasury_acc.clone(),
+            src20_fee_vault_acc.clone(),
+          ],
+        )?;
+        // Burn what is left so that the src20 supply ends at zero, then close the accounts
+        for src20_acc in [src20_treasury_acc, src20_fee_vault_acc].iter() {
+          let src20_data = Src20Account::unpack(&src20_acc.data.borrow())?;
+          if src20_data.amount > 0 {
+            let burn_ix = ISRC20::burn(
+              *src20_token_program.key,
+              *token_owner_acc.key,
+              *src20_token_acc.key,
+              *src20_acc.key,
+              src20_data.amount,
+            )?;
+            invoke_signed(
+              &burn_ix,
+              &[
+                src20_token_program.clone(),
+                token_owner_acc.clone(),
+                src20_token_acc.clone(),
+                (*src20_acc).clone(),
+              ],
+              &[&seed],
+            )?;
+          }
+          let account_destruction_ix = ISRC20::account_destruction(
+            *src20_token_program.key,
+            *token_owner_acc.key,
+            *src20_token_acc.key,
+            *src20_acc.key,
+          )?;
+          invoke_signed(
+            &account_destruction_ix,
+            &[
+              src20_token_program.clone(),
+              token_owner_acc.clone(),
+              src20_token_acc.clone(),
+              (*src20_acc).clone(),
+            ],
+            &[&seed],
+          )?;
+        }
+
+        // Return SPL funds, then close the SPL accounts
+        for spl_acc in [spl_treasury_acc, spl_fee_vault_acc].iter() {
+          let spl_data = Account::unpack(&spl_acc.data.borrow())?;
+          if spl_data.amount > 0 {
+            let transfer_ix = ISPL::transfer(
+              *spl_token_program.key,
+              *token_owner_acc.key,
+              &[],
+              *spl_acc.key,
+              *spl_dst_acc.key,
+              spl_data.amount,
+            )?;
+            invoke_signed(
+              &transfer_ix,
+              &[
+                spl_token_program.clone(),
+                token_owner_acc.clone(),
+                (*spl_acc).clone(),
+                spl_dst_acc.clone(),
+              ],
+              &[&seed],
+            )?;
+          }
+          let close_account_ix = ISPL::close_account(
+            *spl_token_program.key,
+            *token_owner_acc.key,
+            &[],
+            *spl_acc.key,
+            *authority.key,
+          )?;
+          invoke_signed(
+            &close_account_ix,
+            &[
+              spl_token_program.clone(),
+              token_owner_acc.clone(),
+              (*spl_acc).clone(),
+              authority.clone(),
+            ],
+            &[&seed],
+          )?;
+        }
+
+        // The destroyed src20 accounts refunded the wrapper authority
+        let refund = token_owner_acc.lamports();
+        if refund > 0 {
+          let refund_ix = system_instruction::transfer(token_owner_acc.key, authority.key, refund);
+          invoke_signed(
+            &refund_ix,
+            &[
+              system_program.clone(),
+              token_owner_acc.clone(),
+              authority.clone(),
+            ],
+            &[&seed],
+          )?;
+        }
+
+        wrapper_data.paused = true;
+        wrapper_data.decommissioned = true;
+        Wrapper::pack(wrapper_data, &mut wrapper_acc.data.borrow_mut())?;
+
+        Ok(())
+      }
+
+      AppInstruction::WrapSol {
+        lamports,
+        max_fee_bps,
+      } => {
+        info!("Calling WrapSol function");
+        let accounts_iter = &mut accounts.iter();
+        let owner = next_account_info(accounts_iter)?;
+        let dst_acc = next_account_info(accounts_iter)?;
+        let wrapper_acc = next_account_info(accounts_iter)?;
+        let token_owner_acc = next_account_info(accounts_iter)?; // For both types
+        let src20_treasury_acc = next_account_info(accounts_iter)?;
+        let src20_token_acc = next_account_info(accounts_iter)?;
+        let src20_token_program = next_account_info(accounts_iter)?;
+        let spl_treasury_acc = next_account_info(accounts_iter)?;
+        let spl_token_acc = next_account_info(accounts_iter)?;
+        let spl_token_program = next_account_info(accounts_iter)?;
+        let fee_vault_acc = next_account_info(accounts_iter)?;
+        let system_program = next_account_info(accounts_iter)?;
+        let sysvar_clock_acc = next_account_info(accounts_iter)?;
+        if wrapper_acc.owner != program_id || *system_program.key != system_program::id() {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+
+        let mut wrapper_data = Wrapper::unpack(&wrapper_acc.data.borrow())?;
+        let seed: &[&[_]] = &[
+          &wrapper_acc.key.to_bytes()[..],
+          &wrapper_data.authority_bump()[..],
+        ];
+        let token_owner_key = Pubkey::create_program_address(&seed, program_id)?;
+        if !owner.is_signer || token_owner_key != *token_owner_acc.key {
+          return Err(AppError::InvalidOwner.into());
+        }
+        if wrapper_data.paused {
+          return Err(AppError::Paused.into());
+        }
+        wrapper_data.check_fee(max_fee_bps)?;
+        if wrapper_data.src20_treasury != *src20_treasury_acc.key
+          || wrapper_data.src20_token != *src20_token_acc.key
+          || wrapper_data.spl_treasury != *spl_treasury_acc.key
+          || wrapper_data.spl_token != *spl_token_acc.key
+          || wrapper_data.src20_fee_vault != *fee_vault_acc.key
+        {
+          return Err(AppError::UnmatchedWrapper.into());
+        }
+        let (src20_program, spl_program) =
+          wrapper_data.programs(src20_token_acc.owner, spl_token_acc.owner);
+        if src20_program != *src20_token_program.key
+          || spl_program != *spl_token_program.key
+          || dst_acc.owner != src20_token_program.key
+        {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+        if wrapper_data.spl_token != native_mint::id() {
+          return Err(AppError::NotNative.into());
+        }
+        let spl_amount = lamports;
+        let src20_amount = wrapper_data.spl_to_src20(spl_amount)?;
+        // Count against the rate limit of the current window
+        let clock = Clock::from_account_info(sysvar_clock_acc)?;
+        wrapper_data.consume_limit(clock.unix_timestamp, true, spl_amount)?;
+        Wrapper::pack(wrapper_data, &mut wrapper_acc.data.borrow_mut())?;
+
+        // Deposit the lamports straight into the native treasury and sync its amount
+        let deposit_ix = system_instruction::transfer(owner.key, spl_treasury_acc.key, spl_amount);
+        invoke(
+          &deposit_ix,
+          &[
+            system_program.clone(),
+            owner.clone(),
+            spl_treasury_acc.clone(),
+          ],
+        )?;
+        let sync_native_ix = ISPL::sync_native(*spl_token_program.key, *spl_treasury_acc.key)?;
+        invoke(
+          &sync_native_ix,
+          &[spl_token_program.clone(), spl_treasury_acc.clone()],
+        )?;
+
+        // The src20 is minted against the deposit, the fee into the fee vault
+        let fee = wrapper_data.fee(src20_amount).ok_or(AppError::Overflow)?;
+        for (acc, amount) in [(dst_acc, src20_amount - fee), (fee_vault_acc, fee)].iter() {
+          Self::issue_src20(
+            &wrapper_data,
+            token_owner_acc,
+            src20_treasury_acc,
+            src20_token_acc,
+            src20_token_program,
+            acc,
+            *amount,
+            seed,
+          )?;
+        }
+
+        Ok(())
+      }
+
+      AppInstruction::UnwrapToSol {
+        amount,
+        max_fee_bps,
+      } => {
+        info!("Calling UnwrapToSol function");
+        let accounts_iter = &mut accounts.iter();
+        let owner = next_account_info(accounts_iter)?;
+        let src_acc = next_account_info(accounts_iter)?;
+        let wrapper_acc = next_account_info(accounts_iter)?;
+        let token_owner_acc = next_account_info(accounts_iter)?; // For both types
+        let src20_treasury_acc = next_account_info(accounts_iter)?;
+        let src20_token_acc = next_account_info(accounts_iter)?;
+        let src20_token_program = next_account_info(accounts_iter)?;
+        let spl_treasury_acc = next_account_info(accounts_iter)?;
+        let spl_token_acc = next_account_info(accounts_iter)?;
+        let spl_token_program = next_account_info(accounts_iter)?;
+        let fee_vault_acc = next_account_info(accounts_iter)?;
+        let temporary_acc = next_account_info(accounts_iter)?;
         let sysvar_rent_acc = next_account_info(accounts_iter)?;
+        let sysvar_clock_acc = next_account_info(accounts_iter)?;
         if wrapper_acc.owner != program_id {
           return Err(AppError::IncorrectProgramId.into());
         }
 
-        let seed: &[&[_]] = &[&wrapper_acc.key.to_bytes()[..]];
+        let mut wrapper_data = Wrapper::unpack(&wrapper_acc.data.borrow())?;
+        let seed: &[&[_]] = &[
+          &wrapper_acc.key.to_bytes()[..],
+          &wrapper_data.authority_bump()[..],
+        ];
         let token_owner_key = Pubkey::create_program_address(&seed, program_id)?;
+        if !owner.is_signer || token_owner_key != *token_owner_acc.key {
+          return Err(AppError::InvalidOwner.into());
+        }
+        if wrapper_data.paused {
+          return Err(AppError::Paused.into());
+        }
+        wrapper_data.check_fee(max_fee_bps)?;
+        if wrapper_data.src20_treasury != *src20_treasury_acc.key
+          || wrapper_data.src20_token != *src20_token_acc.key
+          || wrapper_data.spl_treasury != *spl_treasury_acc.key
+          || wrapper_data.spl_token != *spl_token_acc.key
+          || wrapper_data.spl_fee_vault != *fee_vault_acc.key
+        {
+          return Err(AppError::UnmatchedWrapper.into());
+        }
+        let (src20_program, spl_program) =
+          wrapper_data.programs(src20_token_acc.owner, spl_token_acc.owner);
+        if src20_program != *src20_token_program.key
+          || spl_program != *spl_token_program.key
+          || src_acc.owner != src20_token_program.key
+          || temporary_acc.owner != spl_token_program.key
+        {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+        if wrapper_data.spl_token != native_mint::id() {
+          return Err(AppError::NotNative.into());
+        }
+        let src20_amount = amount;
+        let spl_amount = wrapper_data.src20_to_spl(src20_amount)?;
+        // Count against the rate limit of the current window
+        let clock = Clock::from_account_info(sysvar_clock_acc)?;
+        wrapper_data.consume_limit(clock.unix_timestamp, false, spl_amount)?;
+        Wrapper::pack(wrapper_data, &mut wrapper_acc.data.borrow_mut())?;
+        let spl_treasury_data = Account::unpack(&spl_treasury_acc.data.borrow())?;
+        if spl_treasury_data.amount < spl_amount {
+          return Err(AppError::InsufficientFunds.into());
+        }
+
+        // The src20 is burnt, the supply follows what is left wrapped
+        Self::retire_src20(
+          &wrapper_data,
+          owner,
+          src20_treasury_acc,
+          src20_token_acc,
+          src20_token_program,
+          src_acc,
+          src20_amount,
+        )?;
+
+        // Move the SOL out through a temporary native account closed to the owner
+        let initialize_account_ix = ISPL::initialize_account(
+          *spl_token_program.key,
+          token_owner_key,
+          *spl_token_acc.key,
+          *temporary_acc.key,
+          *sysvar_rent_acc.key,
+        )?;
+        invoke(
+          &initialize_account_ix,
+          &[
+            spl_token_program.clone(),
+            token_owner_acc.clone(),
+            spl_token_acc.clone(),
+            temporary_acc.clone(),
+            sysvar_rent_acc.clone(),
+          ],
+        )?;
+        // The fee is taken in SPL and kept in the fee vault
+        let fee = wrapper_data.fee(spl_amount).ok_or(AppError::Overflow)?;
+        for (acc, amount) in [(temporary_acc, spl_amount - fee), (fee_vault_acc, fee)].iter() {
+          if *amount == 0 {
+            continue;
+          }
+          let out_ix = ISPL::transfer(
+            *spl_token_program.key,
+            *token_owner_acc.key,
+            &[],
+            *spl_treasury_acc.key,
+            *acc.key,
+            *amount,
+          )?;
+          invoke_signed(
+            &out_ix,
+            &[
+              spl_token_program.clone(),
+              token_owner_acc.clone(),
+              spl_treasury_acc.clone(),
+              (*acc).clone(),
+            ],
+            &[&seed],
+          )?;
+        }
+        let close_account_ix = ISPL::close_account(
+          *spl_token_program.key,
+          *token_owner_acc.key,
+          &[],
+          *temporary_acc.key,
+          *owner.key,
+        )?;
+        invoke_signed(
+          &close_account_ix,
+          &[
+            spl_token_program.clone(),
+            token_owner_acc.clone(),
+            temporary_acc.clone(),
+            owner.clone(),
+          ],
+          &[&seed],
+        )?;
+
+        Ok(())
+      }
+
+      AppInstruction::ReverseConstructor {} => {
+        info!("Calling ReverseConstructor function");
+        let accounts_iter = &mut accounts.iter();
+        let wrapper_acc = next_account_info(accounts_iter)?;
+        let token_owner_acc = next_account_info(accounts_iter)?; // For both types
+        let src20_treasury_acc = next_account_info(accounts_iter)?;
+        let src20_token_acc = next_account_info(accounts_iter)?;
+        let src20_token_program = next_account_info(accounts_iter)?;
+        let spl_token_acc = next_account_info(accounts_iter)?;
+        let spl_token_program = next_account_info(accounts_iter)?;
+        let sysvar_rent_acc = next_account_info(accounts_iter)?;
+        if wrapper_acc.owner != program_id
+          || *spl_token_program.key != ispl::id()
+          || spl_token_acc.owner != spl_token_program.key
+          || !src20_token_program.executable
+          || src20_token_acc.owner != src20_token_program.key
+          || src20_treasury_acc.owner != src20_token_program.key
+        {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+
+        let (token_owner_key, bump) =
+          Pubkey::find_program_address(&[&wrapper_acc.key.to_bytes()[..]], program_id);
+        let seed: &[&[_]] = &[&wrapper_acc.key.to_bytes()[..], &[bump]];
         if !wrapper_acc.is_signer || token_owner_key != *token_owner_acc.key {
           return Err(AppError::InvalidOwner.into());
         }
-        let mut wrapper_data = Wrapper::unpack_unchecked(&wrapper_acc.data.borrow())?;
+        let mut wrapper_data = ReverseWrapper::unpack_unchecked(&wrapper_acc.data.borrow())?;
         if wrapper_data.is_initialized() {
           return Err(AppError::ConstructorOnce.into());
         }
-        let spl_token_data = Mint::unpack(&spl_token_acc.data.borrow())?;
+        let src20_token_data = Token::unpack(&src20_token_acc.data.borrow())?;
 
-        let token_constructor_ix = ISRC20::token_constructor(
+        // The treasury locking the src20 token
+        let account_constructor_ix = ISRC20::account_constructor(
           *src20_token_program.key,
           token_owner_key,
           *src20_token_acc.key,
           *src20_treasury_acc.key,
-          symbol,
-          spl_token_data.supply,
-          spl_token_data.decimals,
         )?;
         invoke_signed(
-          &token_constructor_ix,
+          &account_constructor_ix,
           &[
             src20_token_program.clone(),
             token_owner_acc.clone(),
@@ -68,20 +1068,20 @@ impl Processor {
           &[&seed],
         )?;
 
-        let initialize_account_ix = ISPL::initialize_account(
+        // The SPL mint issued by the wrapper authority, with the same precision
+        let initialize_mint_ix = ISPL::initialize_mint(
           *spl_token_program.key,
-          token_owner_key,
           *spl_token_acc.key,
-          *spl_treasury_acc.key,
+          token_owner_key,
+          None,
+          src20_token_data.decimals,
           *sysvar_rent_acc.key,
         )?;
         invoke(
-          &initialize_account_ix,
+          &initialize_mint_ix,
           &[
             spl_token_program.clone(),
-            token_owner_acc.clone(),
             spl_token_acc.clone(),
-            spl_treasury_acc.clone(),
             sysvar_rent_acc.clone(),
           ],
         )?;
@@ -89,16 +1089,18 @@ impl Processor {
         // Add wrapper data
         wrapper_data.src20_treasury = *src20_treasury_acc.key;
         wrapper_data.src20_token = *src20_token_acc.key;
-        wrapper_data.spl_treasury = *spl_treasury_acc.key;
+        wrapper_data.src20_program = *src20_token_program.key;
         wrapper_data.spl_token = *spl_token_acc.key;
+        wrapper_data.spl_program = *spl_token_program.key;
+        wrapper_data.bump = bump;
         wrapper_data.initialized = true;
-        Wrapper::pack(wrapper_data, &mut wrapper_acc.data.borrow_mut())?;
+        ReverseWrapper::pack(wrapper_data, &mut wrapper_acc.data.borrow_mut())?;
 
         Ok(())
       }
 
-      AppInstruction::Wrap { amount } => {
-        info!("Calling Wrap function");
+      AppInstruction::ReverseWrap { amount } => {
+        info!("Calling ReverseWrap function");
         let accounts_iter = &mut accounts.iter();
         let owner = next_account_info(accounts_iter)?;
         let src_acc = next_account_info(accounts_iter)?;
@@ -108,60 +1110,66 @@ impl Processor {
         let src20_treasury_acc = next_account_info(accounts_iter)?;
         let src20_token_acc = next_account_info(accounts_iter)?;
         let src20_token_program = next_account_info(accounts_iter)?;
-        let spl_treasury_acc = next_account_info(accounts_iter)?;
         let spl_token_acc = next_account_info(accounts_iter)?;
         let spl_token_program = next_account_info(accounts_iter)?;
         if wrapper_acc.owner != program_id {
           return Err(AppError::IncorrectProgramId.into());
         }
 
-        let seed: &[&[_]] = &[&wrapper_acc.key.to_bytes()[..]];
+        let wrapper_data = ReverseWrapper::unpack(&wrapper_acc.data.borrow())?;
+        let seed: &[&[_]] = &[&wrapper_acc.key.to_bytes()[..], &[wrapper_data.bump]];
         let token_owner_key = Pubkey::create_program_address(&seed, program_id)?;
         if !owner.is_signer || token_owner_key != *token_owner_acc.key {
           return Err(AppError::InvalidOwner.into());
         }
-        let wrapper_data = Wrapper::unpack(&wrapper_acc.data.borrow())?;
         if wrapper_data.src20_treasury != *src20_treasury_acc.key
           || wrapper_data.src20_token != *src20_token_acc.key
-          || wrapper_data.spl_treasury != *spl_treasury_acc.key
           || wrapper_data.spl_token != *spl_token_acc.key
         {
           return Err(AppError::UnmatchedWrapper.into());
         }
+        if wrapper_data.src20_program != *src20_token_program.key
+          || wrapper_data.spl_program != *spl_token_program.key
+        {
+          return Err(AppError::IncorrectProgramId.into());
+        }
 
-        let in_ix = ISPL::transfer(
-          *spl_token_program.key,
+        // Lock src20 in the treasury
+        let in_ix = ISRC20::transfer(
+          *src20_token_program.key,
           *owner.key,
+          *src20_token_acc.key,
           *src_acc.key,
-          *spl_treasury_acc.key,
+          *src20_treasury_acc.key,
           amount,
         )?;
         invoke(
           &in_ix,
           &[
-            spl_token_program.clone(),
+            src20_token_program.clone(),
             owner.clone(),
+            src20_token_acc.clone(),
             src_acc.clone(),
-            spl_treasury_acc.clone(),
+            src20_treasury_acc.clone(),
           ],
         )?;
 
-        let out_ix = ISRC20::transfer(
-          *src20_token_program.key,
+        // Issue the same amount of SPL
+        let mint_to_ix = ISPL::mint_to(
+          *spl_token_program.key,
           *token_owner_acc.key,
-          *src20_token_acc.key,
-          *src20_treasury_acc.key,
+          &[],
+          *spl_token_acc.key,
           *dst_acc.key,
           amount,
         )?;
         invoke_signed(
-          &out_ix,
+          &mint_to_ix,
           &[
-            src20_token_program.clone(),
-            token_owner_acc.clone(),
-            src20_token_acc.clone(),
-            src20_treasury_acc.clone(),
+            spl_token_program.clone(),
+            spl_token_acc.clone(),
             dst_acc.clone(),
+            token_owner_acc.clone(),
           ],
           &[&seed],
         )?;
@@ -169,8 +1177,8 @@ impl Processor {
         Ok(())
       }
 
-      AppInstruction::Unwrap { amount } => {
-        info!("Calling Unwrap function");
+      AppInstruction::ReverseUnwrap { amount } => {
+        info!("Calling ReverseUnwrap function");
         let accounts_iter = &mut accounts.iter();
         let owner = next_account_info(accounts_iter)?;
         let src_acc = next_account_info(accounts_iter)?;
@@ -180,59 +1188,65 @@ impl Processor {
         let src20_treasury_acc = next_account_info(accounts_iter)?;
         let src20_token_acc = next_account_info(accounts_iter)?;
         let src20_token_program = next_account_info(accounts_iter)?;
-        let spl_treasury_acc = next_account_info(accounts_iter)?;
         let spl_token_acc = next_account_info(accounts_iter)?;
         let spl_token_program = next_account_info(accounts_iter)?;
         if wrapper_acc.owner != program_id {
           return Err(AppError::IncorrectProgramId.into());
         }
 
-        let seed: &[&[_]] = &[&wrapper_acc.key.to_bytes()[..]];
+        let wrapper_data = ReverseWrapper::unpack(&wrapper_acc.data.borrow())?;
+        let seed: &[&[_]] = &[&wrapper_acc.key.to_bytes()[..], &[wrapper_data.bump]];
         let token_owner_key = Pubkey::create_program_address(&seed, program_id)?;
         if !owner.is_signer || token_owner_key != *token_owner_acc.key {
           return Err(AppError::InvalidOwner.into());
         }
-        let wrapper_data = Wrapper::unpack(&wrapper_acc.data.borrow())?;
         if wrapper_data.src20_treasury != *src20_treasury_acc.key
           || wrapper_data.src20_token != *src20_token_acc.key
-          || wrapper_data.spl_treasury != *spl_treasury_acc.key
           || wrapper_data.spl_token != *spl_token_acc.key
         {
           return Err(AppError::UnmatchedWrapper.into());
         }
+        if wrapper_data.src20_program != *src20_token_program.key
+          || wrapper_data.spl_program != *spl_token_program.key
+        {
+          return Err(AppError::IncorrectProgramId.into());
+        }
 
-        let in_ix = ISRC20::transfer(
-          *src20_token_program.key,
+        // Burn the SPL
+        let burn_ix = ISPL::burn(
+          *spl_token_program.key,
           *owner.key,
-          *src20_token_acc.key,
+          &[],
           *src_acc.key,
-          *src20_treasury_acc.key,
+          *spl_token_acc.key,
           amount,
         )?;
         invoke(
-          &in_ix,
+          &burn_ix,
           &[
-            src20_token_program.clone(),
-            owner.clone(),
-            src20_token_acc.clone(),
+            spl_token_program.clone(),
             src_acc.clone(),
-            src20_treasury_acc.clone(),
+            spl_token_acc.clone(),
+            owner.clone(),
           ],
         )?;
 
-        let out_ix = ISPL::transfer(
-          *spl_token_program.key,
+        // Release the locked src20
+        let out_ix = ISRC20::transfer(
+          *src20_token_program.key,
           *token_owner_acc.key,
-          *spl_treasury_acc.key,
+          *src20_token_acc.key,
+          *src20_treasury_acc.key,
           *dst_acc.key,
           amount,
         )?;
         invoke_signed(
           &out_ix,
           &[
-            spl_token_program.clone(),
+            src20_token_program.clone(),
             token_owner_acc.clone(),
-            spl_treasury_acc.clone(),
+            src20_token_acc.clone(),
+            src20_treasury_acc.clone(),
             dst_acc.clone(),
           ],
           &[&seed],
@@ -240,6 +1254,419 @@ impl Processor {
 
         Ok(())
       }
+
+      AppInstruction::ProveReserves {} => {
+        info!("Calling ProveReserves function");
+        let accounts_iter = &mut accounts.iter();
+        let wrapper_acc = next_account_info(accounts_iter)?;
+        let src20_treasury_acc = next_account_info(accounts_iter)?;
+        let src20_token_acc = next_account_info(accounts_iter)?;
+        let spl_treasury_acc = next_account_info(accounts_iter)?;
+        if wrapper_acc.owner != program_id {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+
+        let wrapper_data = Wrapper::unpack(&wrapper_acc.data.borrow())?;
+        if wrapper_data.src20_treasury != *src20_treasury_acc.key
+          || wrapper_data.src20_token != *src20_token_acc.key
+          || wrapper_data.spl_treasury != *spl_treasury_acc.key
+        {
+          return Err(AppError::UnmatchedWrapper.into());
+        }
+        let (src20_program, spl_program) =
+          wrapper_data.programs(src20_token_acc.owner, spl_treasury_acc.owner);
+        if *src20_treasury_acc.owner != src20_program
+          || *src20_token_acc.owner != src20_program
+          || *spl_treasury_acc.owner != spl_program
+        {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+
+        // Read-only, compare in src20 units so that flooring never overstates the reserve
+        let src20_token_data = Token::unpack(&src20_token_acc.data.borrow())?;
+        let src20_treasury_data = Src20Account::unpack(&src20_treasury_acc.data.borrow())?;
+        let spl_treasury_data = Account::unpack(&spl_treasury_acc.data.borrow())?;
+        let circulating = src20_token_data
+          .total_supply
+          .checked_sub(src20_treasury_data.amount)
+          .ok_or(AppError::Overflow)?;
+        let reserve = wrapper_data
+          .spl_to_src20_floor(spl_treasury_data.amount)
+          .ok_or(AppError::Overflow)?;
+        let collateralized = reserve >= circulating;
+        info!(&format!(
+          "ProveReserves: wrapper {}, spl reserve {}, src20 supply {}, src20 treasury {}, circulating {}, collateralized {}",
+          wrapper_acc.key,
+          spl_treasury_data.amount,
+          src20_token_data.total_supply,
+          src20_treasury_data.amount,
+          circulating,
+          collateralized
+        ));
+        if !collateralized {
+          return Err(AppError::Undercollateralized.into());
+        }
+
+        Ok(())
+      }
+
+      AppInstruction::SetRateLimit {
+        window_duration,
+        wrap_limit,
+        unwrap_limit,
+      } => {
+        info!("Calling SetRateLimit function");
+        let accounts_iter = &mut accounts.iter();
+        let authority = next_account_info(accounts_iter)?;
+        let wrapper_acc = next_account_info(accounts_iter)?;
+        if wrapper_acc.owner != program_id {
+          return Err(AppError::IncorrectProgramId.into());
+        }
+
+        let mut wrapper_data = Wrapper::unpack(&wrapper_acc.data.borrow())?;
+        if !authority.is_signer || wrapper_data.authority != *authority.key {
+          return Err(AppError::InvalidOwner.into());
+        }
+        // A zero window disables the limits, it cannot come with any
+        if window_duration < 0 || (window_duration == 0 && (wrap_limit > 0 || unwrap_limit > 0)) {
+          return Err(AppError::InvalidInstruction.into());
+        }
+        wrapper_data.window_duration = window_duration;
+        wrapper_data.wrap_limit = wrap_limit;
+        wrapper_data.unwrap_limit = unwrap_limit;
+        Wrapper::pack(wrapper_data, &mut wrapper_acc.data.borrow_mut())?;
+
+        Ok(())
+      }
+    }
+  }
+
+  /// Construct a wrapper, the signer seeds sign for accounts created by the bootstrap
+  pub fn construct(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    symbol: [char; 4],
+    decimals: u8,
+    signer_seeds: &[&[&[u8]]],
+  ) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let wrapper_acc = next_account_info(accounts_iter)?;
+    let token_owner_acc = next_account_info(accounts_iter)?; // For both types
+    let src20_treasury_acc = next_account_info(accounts_iter)?;
+    let src20_token_acc = next_account_info(accounts_iter)?;
+    let src20_token_program = next_account_info(accounts_iter)?;
+    let spl_treasury_acc = next_account_info(accounts_iter)?;
+    let spl_token_acc = next_account_info(accounts_iter)?;
+    let spl_token_program = next_account_info(accounts_iter)?;
+    let sysvar_rent_acc = next_account_info(accounts_iter)?;
+    let fee_authority = next_account_info(accounts_iter)?;
+    let src20_fee_vault_acc = next_account_info(accounts_iter)?;
+    let spl_fee_vault_acc = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let registry_acc = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let authority = next_account_info(accounts_iter)?;
+    if wrapper_acc.owner != program_id
+      || *system_program.key != system_program::id()
+      || *spl_token_program.key != ispl::id()
+      || spl_token_acc.owner != spl_token_program.key
+      || spl_treasury_acc.owner != spl_token_program.key
+      || spl_fee_vault_acc.owner != spl_token_program.key
+      || !src20_token_program.executable
+      || src20_token_acc.owner != src20_token_program.key
+      || src20_treasury_acc.owner != src20_token_program.key
+      || src20_fee_vault_acc.owner != src20_token_program.key
+    {
+      return Err(AppError::IncorrectProgramId.into());
+    }
+
+    // The canonical bump always yields an off-curve address
+    let (token_owner_key, bump) =
+      Pubkey::find_program_address(&[&wrapper_acc.key.to_bytes()[..]], program_id);
+    let seed: &[&[_]] = &[&wrapper_acc.key.to_bytes()[..], &[bump]];
+    let mut signers = vec![seed];
+    signers.extend_from_slice(signer_seeds);
+    if !wrapper_acc.is_signer || token_owner_key != *token_owner_acc.key {
+      return Err(AppError::InvalidOwner.into());
+    }
+    // Only full-length src20 tokens record the wrapper authority allowed to mint
+    if src20_token_acc.data_len() != Token::LEN {
+      return Err(AppError::UnmatchedWrapper.into());
+    }
+    let mut wrapper_data = Wrapper::unpack_unchecked(&wrapper_acc.data.borrow())?;
+    if wrapper_data.is_initialized() {
+      return Err(AppError::ConstructorOnce.into());
+    }
+    // A single wrapper per SPL mint, the registry already exists otherwise
+    let (registry_key, registry_bump) =
+      Registry::find_address(spl_token_acc.key, src20_token_program.key, program_id);
+    if registry_key != *registry_acc.key {
+      return Err(AppError::UnmatchedWrapper.into());
+    }
+    if !payer.is_signer {
+      return Err(AppError::InvalidOwner.into());
+    }
+    // Lamports alone do not take the registry, anyone could send them beforehand
+    if registry_acc.owner == program_id
+      && Registry::unpack_unchecked(&registry_acc.data.borrow())?.is_initialized()
+    {
+      return Err(AppError::ConstructorOnce.into());
+    }
+    let rent = Rent::from_account_info(sysvar_rent_acc)?;
+    Self::create_derived_account(
+      payer,
+      registry_acc,
+      system_program,
+      &rent,
+      Registry::LEN,
+      program_id,
+      &[&[
+        &spl_token_acc.key.to_bytes()[..],
+        &src20_token_program.key.to_bytes()[..],
+        &[registry_bump],
+      ]],
+    )?;
+    let registry_data = Registry {
+      wrapper: *wrapper_acc.key,
+      initialized: true,
+    };
+    Registry::pack(registry_data, &mut registry_acc.data.borrow_mut())?;
+    let spl_token_data = Mint::unpack(&spl_token_acc.data.borrow())?;
+    wrapper_data.spl_decimals = spl_token_data.decimals;
+    wrapper_data.src20_decimals = decimals;
+
+    // The src20 supply starts empty, Wrap mints and Unwrap burns as the wrapper authority
+    let token_constructor_ix = ISRC20::token_constructor(
+      *src20_token_program.key,
+      token_owner_key,
+      *src20_token_acc.key,
+      *src20_treasury_acc.key,
+      symbol,
+      0,
+      decimals,
+    )?;
+    invoke_signed(
+      &token_constructor_ix,
+      &[
+        src20_token_program.clone(),
+        token_owner_acc.clone(),
+        src20_token_acc.clone(),
+        src20_treasury_acc.clone(),
+      ],
+      &signers,
+    )?;
+
+    let initialize_account_ix = ISPL::initialize_account(
+      *spl_token_program.key,
+      token_owner_key,
+      *spl_token_acc.key,
+      *spl_treasury_acc.key,
+      *sysvar_rent_acc.key,
+    )?;
+    invoke(
+      &initialize_account_ix,
+      &[
+        spl_token_program.clone(),
+        token_owner_acc.clone(),
+        spl_token_acc.clone(),
+        spl_treasury_acc.clone(),
+        sysvar_rent_acc.clone(),
+      ],
+    )?;
+
+    // Fee vaults are held by the wrapper authority, one on each side
+    let account_constructor_ix = ISRC20::account_constructor(
+      *src20_token_program.key,
+      token_owner_key,
+      *src20_token_acc.key,
+      *src20_fee_vault_acc.key,
+    )?;
+    invoke_signed(
+      &account_constructor_ix,
+      &[
+        src20_token_program.clone(),
+        token_owner_acc.clone(),
+        src20_token_acc.clone(),
+        src20_fee_vault_acc.clone(),
+      ],
+      &signers,
+    )?;
+    let initialize_fee_vault_ix = ISPL::initialize_account(
+      *spl_token_program.key,
+      token_owner_key,
+      *spl_token_acc.key,
+      *spl_fee_vault_acc.key,
+      *sysvar_rent_acc.key,
+    )?;
+    invoke(
+      &initialize_fee_vault_ix,
+      &[
+        spl_token_program.clone(),
+        token_owner_acc.clone(),
+        spl_token_acc.clone(),
+        spl_fee_vault_acc.clone(),
+        sysvar_rent_acc.clone(),
+      ],
+    )?;
+
+    // Add wrapper data
+    wrapper_data.src20_treasury = *src20_treasury_acc.key;
+    wrapper_data.src20_token = *src20_token_acc.key;
+    wrapper_data.src20_program = *src20_token_program.key;
+    wrapper_data.spl_treasury = *spl_treasury_acc.key;
+    wrapper_data.spl_token = *spl_token_acc.key;
+    wrapper_data.spl_program = *spl_token_program.key;
+    wrapper_data.bump = bump;
+    wrapper_data.fee_authority = *fee_authority.key;
+    wrapper_data.fee_bps = 0;
+    wrapper_data.src20_fee_vault = *src20_fee_vault_acc.key;
+    wrapper_data.spl_fee_vault = *spl_fee_vault_acc.key;
+    wrapper_data.authority = *authority.key;
+    wrapper_data.paused = false;
+    wrapper_data.decommissioned = false;
+    wrapper_data.initialized = true;
+    Wrapper::pack(wrapper_data, &mut wrapper_acc.data.borrow_mut())?;
+
+    Ok(())
+  }
+
+  /// Credit src20 as the wrapper authority, minted, or drawn from the treasury a legacy
+  /// wrapper filled with its whole supply at construction
+  #[allow(clippy::too_many_arguments)]
+  pub fn issue_src20<'a>(
+    wrapper_data: &Wrapper,
+    token_owner_acc: &AccountInfo<'a>,
+    src20_treasury_acc: &AccountInfo<'a>,
+    src20_token_acc: &AccountInfo<'a>,
+    src20_token_program: &AccountInfo<'a>,
+    dst_acc: &AccountInfo<'a>,
+    amount: u64,
+    seed: &[&[u8]],
+  ) -> ProgramResult {
+    if amount == 0 {
+      return Ok(());
+    }
+    if wrapper_data.is_legacy() {
+      let transfer_ix = ISRC20::transfer(
+        *src20_token_program.key,
+        *token_owner_acc.key,
+        *src20_token_acc.key,
+        *src20_treasury_acc.key,
+        *dst_acc.key,
+        amount,
+      )?;
+      return invoke_signed(
+        &transfer_ix,
+        &[
+          src20_token_program.clone(),
+          token_owner_acc.clone(),
+          src20_token_acc.clone(),
+          src20_treasury_acc.clone(),
+          dst_acc.clone(),
+        ],
+        &[seed],
+      );
+    }
+    let mint_to_ix = ISRC20::mint_to(
+      *src20_token_program.key,
+      *token_owner_acc.key,
+      *src20_token_acc.key,
+      *dst_acc.key,
+      amount,
+    )?;
+    invoke_signed(
+      &mint_to_ix,
+      &[
+        src20_token_program.clone(),
+        token_owner_acc.clone(),
+        src20_token_acc.clone(),
+        dst_acc.clone(),
+      ],
+      &[seed],
+    )
+  }
+
+  /// Take src20 back from its owner, burnt, or returned to the treasury of a legacy wrapper
+  pub fn retire_src20<'a>(
+    wrapper_data: &Wrapper,
+    owner: &AccountInfo<'a>,
+    src20_treasury_acc: &AccountInfo<'a>,
+    src20_token_acc: &AccountInfo<'a>,
+    src20_token_program: &AccountInfo<'a>,
+    src_acc: &AccountInfo<'a>,
+    amount: u64,
+  ) -> ProgramResult {
+    if wrapper_data.is_legacy() {
+      let transfer_ix = ISRC20::transfer(
+        *src20_token_program.key,
+        *owner.key,
+        *src20_token_acc.key,
+        *src_acc.key,
+        *src20_treasury_acc.key,
+        amount,
+      )?;
+      return invoke(
+        &transfer_ix,
+        &[
+          src20_token_program.clone(),
+          owner.clone(),
+          src20_token_acc.clone(),
+          src_acc.clone(),
+          src20_treasury_acc.clone(),
+        ],
+      );
+    }
+    let burn_ix = ISRC20::burn(
+      *src20_token_program.key,
+      *owner.key,
+      *src20_token_acc.key,
+      *src_acc.key,
+      amount,
+    )?;
+    invoke(
+      &burn_ix,
+      &[
+        src20_token_program.clone(),
+        owner.clone(),
+        src20_token_acc.clone(),
+        src_acc.clone(),
+      ],
+    )
+  }
+
+  /// Create an account, topping up whatever lamports it already holds.
+  /// Derived addresses sign with their seeds, keypairs sign the transaction.
+  pub fn create_derived_account<'a>(
+    payer: &AccountInfo<'a>,
+    target_acc: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    rent: &Rent,
+    space: usize,
+    owner: &Pubkey,
+    signer_seeds: &[&[&[u8]]],
+  ) -> ProgramResult {
+    let required = rent
+      .minimum_balance(space)
+      .saturating_sub(target_acc.lamports());
+    if required > 0 {
+      let transfer_ix = system_instruction::transfer(payer.key, target_acc.key, required);
+      invoke(
+        &transfer_ix,
+        &[system_program.clone(), payer.clone(), target_acc.clone()],
+      )?;
     }
+    let allocate_ix = system_instruction::allocate(target_acc.key, space as u64);
+    invoke_signed(
+      &allocate_ix,
+      &[system_program.clone(), target_acc.clone()],
+      signer_seeds,
+    )?;
+    let assign_ix = system_instruction::assign(target_acc.key, owner);
+    invoke_signed(
+      &assign_ix,
+      &[system_program.clone(), target_acc.clone()],
+      signer_seeds,
+    )?;
+    Ok(())
   }
 }
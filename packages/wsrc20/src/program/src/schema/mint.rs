@@ -72,7 +72,7 @@ impl Pack for Mint {
 }
 
 // Helpers
-fn pack_coption_key(src: &COption<Pubkey>, dst: &mut [u8; 36]) {
+pub fn pack_coption_key(src: &COption<Pubkey>, dst: &mut [u8; 36]) {
   let (tag, body) = mut_array_refs![dst, 4, 32];
   match src {
     COption::Some(key) => {
@@ -84,7 +84,7 @@ fn pack_coption_key(src: &COption<Pubkey>, dst: &mut [u8; 36]) {
     }
   }
 }
-fn unpack_coption_key(src: &[u8; 36]) -> Result<COption<Pubkey>, ProgramError> {
+pub fn unpack_coption_key(src: &[u8; 36]) -> Result<COption<Pubkey>, ProgramError> {
   let (tag, body) = array_refs![src, 4, 32];
   match *tag {
     [0, 0, 0, 0] => Ok(COption::None),
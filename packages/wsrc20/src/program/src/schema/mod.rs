@@ -1,2 +1,7 @@
+pub mod account;
 pub mod mint;
+pub mod registry;
+pub mod reverse_wrapper;
+pub mod src20_account;
+pub mod token;
 pub mod wrapper;
@@ -0,0 +1,75 @@
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use solana_program::{
+  program_error::ProgramError,
+  program_pack::{IsInitialized, Pack, Sealed},
+  pubkey::Pubkey,
+};
+
+//
+// Define the data struct
+//
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Registry {
+  pub wrapper: Pubkey,
+  pub initialized: bool,
+}
+
+impl Registry {
+  /// The registry address of an SPL mint, one wrapper per mint and src20 program
+  pub fn find_address(
+    spl_token: &Pubkey,
+    src20_program: &Pubkey,
+    program_id: &Pubkey,
+  ) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+      &[&spl_token.to_bytes()[..], &src20_program.to_bytes()[..]],
+      program_id,
+    )
+  }
+}
+
+//
+// Implement Sealed trait
+//
+impl Sealed for Registry {}
+
+//
+// Implement IsInitialized trait
+//
+impl IsInitialized for Registry {
+  fn is_initialized(&self) -> bool {
+    self.initialized
+  }
+}
+
+//
+// Implement Pack trait
+//
+impl Pack for Registry {
+  // Fixed length
+  const LEN: usize = 32 + 1;
+  // Unpack data from [u8] to the data struct
+  fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+    let src = array_ref![src, 0, 33];
+    let (wrapper, initialized) = array_refs![src, 32, 1];
+    Ok(Registry {
+      wrapper: Pubkey::new_from_array(*wrapper),
+      initialized: match initialized {
+        [0] => false,
+        [1] => true,
+        _ => return Err(ProgramError::InvalidAccountData),
+      },
+    })
+  }
+  // Pack data from the data struct to [u8]
+  fn pack_into_slice(&self, dst: &mut [u8]) {
+    let dst = array_mut_ref![dst, 0, 33];
+    let (dst_wrapper, dst_initialized) = mut_array_refs![dst, 32, 1];
+    let &Registry {
+      ref wrapper,
+      initialized,
+    } = self;
+    dst_wrapper.copy_from_slice(wrapper.as_ref());
+    *dst_initialized = [initialized as u8];
+  }
+}
@@ -0,0 +1,91 @@
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use solana_program::{
+  program_error::ProgramError,
+  program_pack::{IsInitialized, Pack, Sealed},
+  pubkey::Pubkey,
+};
+
+//
+// Define the data struct
+// The src20 token is locked in the treasury, the SPL mint is issued by the wrapper authority
+//
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ReverseWrapper {
+  pub src20_treasury: Pubkey,
+  pub src20_token: Pubkey,
+  pub src20_program: Pubkey,
+  pub spl_token: Pubkey,
+  pub spl_program: Pubkey,
+  pub bump: u8,
+  pub initialized: bool,
+}
+
+//
+// Implement Sealed trait
+//
+impl Sealed for ReverseWrapper {}
+
+//
+// Implement IsInitialized trait
+//
+impl IsInitialized for ReverseWrapper {
+  fn is_initialized(&self) -> bool {
+    self.initialized
+  }
+}
+
+//
+// Implement Pack trait
+//
+impl Pack for ReverseWrapper {
+  // Fixed length
+  const LEN: usize = 32 * 5 + 1 + 1;
+  // Unpack data from [u8] to the data struct
+  fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+    let src = array_ref![src, 0, 162];
+    let (src20_treasury, src20_token, src20_program, spl_token, spl_program, bump, initialized) =
+      array_refs![src, 32, 32, 32, 32, 32, 1, 1];
+    Ok(ReverseWrapper {
+      src20_treasury: Pubkey::new_from_array(*src20_treasury),
+      src20_token: Pubkey::new_from_array(*src20_token),
+      src20_program: Pubkey::new_from_array(*src20_program),
+      spl_token: Pubkey::new_from_array(*spl_token),
+      spl_program: Pubkey::new_from_array(*spl_program),
+      bump: u8::from_le_bytes(*bump),
+      initialized: match initialized {
+        [0] => false,
+        [1] => true,
+        _ => return Err(ProgramError::InvalidAccountData),
+      },
+    })
+  }
+  // Pack data from the data struct to [u8]
+  fn pack_into_slice(&self, dst: &mut [u8]) {
+    let dst = array_mut_ref![dst, 0, 162];
+    let (
+      dst_src20_treasury,
+      dst_src20_token,
+      dst_src20_program,
+      dst_spl_token,
+      dst_spl_program,
+      dst_bump,
+      dst_initialized,
+    ) = mut_array_refs![dst, 32, 32, 32, 32, 32, 1, 1];
+    let &ReverseWrapper {
+      ref src20_treasury,
+      ref src20_token,
+      ref src20_program,
+      ref spl_token,
+      ref spl_program,
+      bump,
+      initialized,
+    } = self;
+    dst_src20_treasury.copy_from_slice(src20_treasury.as_ref());
+    dst_src20_token.copy_from_slice(src20_token.as_ref());
+    dst_src20_program.copy_from_slice(src20_program.as_ref());
+    dst_spl_token.copy_from_slice(spl_token.as_ref());
+    dst_spl_program.copy_from_slice(spl_program.as_ref());
+    *dst_bump = bump.to_le_bytes();
+    *dst_initialized = [initialized as u8];
+  }
+}
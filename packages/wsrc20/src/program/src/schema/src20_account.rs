@@ -0,0 +1,127 @@
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use solana_program::{
+  program_error::ProgramError,
+  program_pack::{IsInitialized, Pack, Sealed},
+  pubkey::Pubkey,
+};
+
+//
+// Define the data struct
+//
+// A view on the leading fields of a src20 account, the only ones the wrapper reads
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Account {
+  pub owner: Pubkey,
+  pub token: Pubkey,
+  pub amount: u64,
+  pub initialized: bool,
+}
+
+impl Account {
+  /// Length of the src20 accounts created before the extensions were appended
+  pub const LEGACY_LEN: usize = 32 + 32 + 8 + 1;
+}
+
+//
+// Implement Sealed trait
+//
+impl Sealed for Account {}
+
+//
+// Implement IsInitialized trait
+//
+impl IsInitialized for Account {
+  fn is_initialized(&self) -> bool {
+    self.initialized
+  }
+}
+
+//
+// Implement Pack trait
+//
+impl Pack for Account {
+  // Fixed length of the current src20 accounts, extensions included
+  const LEN: usize = Self::LEGACY_LEN + 1;
+  // Legacy src20 accounts are shorter, the view covers both
+  fn unpack_unchecked(input: &[u8]) -> Result<Self, ProgramError> {
+    if input.len() != Self::LEN && input.len() != Self::LEGACY_LEN {
+      return Err(ProgramError::InvalidAccountData);
+    }
+    Self::unpack_from_slice(input)
+  }
+  // Unpack data from [u8] to the data struct
+  fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+    let src = array_ref![src, 0, 73];
+    let (owner, token, amount, initialized) = array_refs![src, 32, 32, 8, 1];
+    Ok(Account {
+      owner: Pubkey::new_from_array(*owner),
+      token: Pubkey::new_from_array(*token),
+      amount: u64::from_le_bytes(*amount),
+      initialized: match initialized {
+        [0] => false,
+        [1] => true,
+        _ => return Err(ProgramError::InvalidAccountData),
+      },
+    })
+  }
+  // Pack data from the data struct to [u8]
+  fn pack_into_slice(&self, dst: &mut [u8]) {
+    let dst = array_mut_ref![dst, 0, 73];
+    let (dst_owner, dst_token, dst_amount, dst_initialized) = mut_array_refs![dst, 32, 32, 8, 1];
+    let &Account {
+      ref owner,
+      ref token,
+      amount,
+      initialized,
+    } = self;
+    dst_owner.copy_from_slice(owner.as_ref());
+    dst_token.copy_from_slice(token.as_ref());
+    *dst_amount = amount.to_le_bytes();
+    *dst_initialized = [initialized as u8];
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // An account as the src20 program lays it out, holding 1_000 of its token
+  fn src20_account(len: usize, owner: &Pubkey, token: &Pubkey) -> Vec<u8> {
+    let mut data = vec![0u8; len];
+    data[..32].copy_from_slice(owner.as_ref());
+    data[32..64].copy_from_slice(token.as_ref());
+    data[64..72].copy_from_slice(&1_000u64.to_le_bytes());
+    data[72] = 1;
+    data
+  }
+
+  #[test]
+  fn reads_the_src20_layout() {
+    assert_eq!((Account::LEGACY_LEN, Account::LEN), (73, 74));
+    let account = Account {
+      owner: Pubkey::new_unique(),
+      token: Pubkey::new_unique(),
+      amount: 1_000,
+      initialized: true,
+    };
+    // The memo requirement follows and is left alone
+    let mut data = src20_account(Account::LEN, &account.owner, &account.token);
+    data[73] = 1;
+    assert_eq!(Account::unpack(&data).unwrap(), account);
+    let legacy = src20_account(Account::LEGACY_LEN, &account.owner, &account.token);
+    assert_eq!(Account::unpack(&legacy).unwrap(), account);
+  }
+
+  #[test]
+  fn rejects_other_lengths() {
+    let data = src20_account(
+      Account::LEN + 1,
+      &Pubkey::new_unique(),
+      &Pubkey::new_unique(),
+    );
+    assert_eq!(
+      Account::unpack(&data),
+      Err(ProgramError::InvalidAccountData)
+    );
+  }
+}
@@ -0,0 +1,122 @@
+use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
+use solana_program::{
+  program_error::ProgramError,
+  program_pack::{IsInitialized, Pack, Sealed},
+};
+
+//
+// Define the data struct
+//
+// A view on the leading fields of a src20 token, the only ones the wrapper reads
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Token {
+  pub total_supply: u64,
+  pub decimals: u8,
+  pub initialized: bool,
+}
+
+impl Token {
+  /// Length of the src20 tokens created before the extensions were appended
+  pub const LEGACY_LEN: usize = 4 * 4 + 8 + 1 + 1;
+}
+
+//
+// Implement Sealed trait
+//
+impl Sealed for Token {}
+
+//
+// Implement IsInitialized trait
+//
+impl IsInitialized for Token {
+  fn is_initialized(&self) -> bool {
+    self.initialized
+  }
+}
+
+//
+// Implement Pack trait
+//
+impl Pack for Token {
+  // Fixed length of the current src20 tokens, extensions included
+  const LEN: usize = Self::LEGACY_LEN + 1 + 32 + 8 + 2 + 8 + 2 + 32;
+  // Legacy src20 tokens are shorter, the view covers both
+  fn unpack_unchecked(input: &[u8]) -> Result<Self, ProgramError> {
+    if input.len() != Self::LEN && input.len() != Self::LEGACY_LEN {
+      return Err(ProgramError::InvalidAccountData);
+    }
+    Self::unpack_from_slice(input)
+  }
+  // Unpack data from [u8] to the data struct
+  fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+    let src = array_ref![src, 0, 26];
+    let (_symbol, total_supply, decimals, initialized) = array_refs![src, 16, 8, 1, 1];
+    Ok(Token {
+      total_supply: u64::from_le_bytes(*total_supply),
+      decimals: u8::from_le_bytes(*decimals),
+      initialized: match initialized {
+        [0] => false,
+        [1] => true,
+        _ => return Err(ProgramError::InvalidAccountData),
+      },
+    })
+  }
+  // Pack data from the data struct to [u8]
+  fn pack_into_slice(&self, dst: &mut [u8]) {
+    let dst = array_mut_ref![dst, 0, 26];
+    let (_dst_symbol, dst_total_supply, dst_decimals, dst_initialized) =
+      mut_array_refs![dst, 16, 8, 1, 1];
+    let &Token {
+      total_supply,
+      decimals,
+      initialized,
+    } = self;
+    *dst_total_supply = total_supply.to_le_bytes();
+    *dst_decimals = decimals.to_le_bytes();
+    *dst_initialized = [initialized as u8];
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // A token as the src20 program lays it out, WSRC with 1_000_000 at 9 decimals
+  fn src20_token(len: usize) -> Vec<u8> {
+    let mut data = vec![0u8; len];
+    for (i, c) in ['W', 'S', 'R', 'C'].iter().enumerate() {
+      data[i * 4..i * 4 + 4].copy_from_slice(&(*c as u32).to_le_bytes());
+    }
+    data[16..24].copy_from_slice(&1_000_000u64.to_le_bytes());
+    data[24] = 9;
+    data[25] = 1;
+    data
+  }
+
+  #[test]
+  fn reads_the_src20_layout() {
+    assert_eq!((Token::LEGACY_LEN, Token::LEN), (26, 111));
+    let token = Token {
+      total_supply: 1_000_000,
+      decimals: 9,
+      initialized: true,
+    };
+    // The extensions follow and are left alone, native then the rate at 77 bytes in
+    let mut data = src20_token(Token::LEN);
+    data[26] = 1;
+    data[77..79].copy_from_slice(&500i16.to_le_bytes());
+    assert_eq!(Token::unpack(&data).unwrap(), token);
+    assert_eq!(
+      Token::unpack(&src20_token(Token::LEGACY_LEN)).unwrap(),
+      token
+    );
+  }
+
+  #[test]
+  fn rejects_other_lengths() {
+    assert_eq!(
+      Token::unpack(&src20_token(Token::LEN + 1)),
+      Err(ProgramError::InvalidAccountData)
+    );
+  }
+}
@@ -1,9 +1,17 @@
+use crate::error::AppError;
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
 use solana_program::{
   program_error::ProgramError,
   program_pack::{IsInitialized, Pack, Sealed},
   pubkey::Pubkey,
 };
+use std::convert::TryInto;
+
+/// Highest fee the fee authority can set, 10%
+pub const MAX_FEE_BPS: u16 = 1_000;
+
+/// Seed of the src20 accounts Wrap opens, next to the owner and the src20 token
+pub const DESTINATION_SEED: &[u8] = b"destination";
 
 //
 // Define the data struct
@@ -15,6 +23,156 @@ pub struct Wrapper {
   pub spl_treasury: Pubkey,
   pub spl_token: Pubkey,
   pub initialized: bool,
+  // Appended after the original layout, absent from legacy wrappers
+  pub src20_program: Pubkey,
+  pub spl_program: Pubkey,
+  pub bump: u8,
+  pub spl_decimals: u8,
+  pub src20_decimals: u8,
+  pub fee_authority: Pubkey,
+  pub fee_bps: u16,
+  pub src20_fee_vault: Pubkey,
+  pub spl_fee_vault: Pubkey,
+  pub authority: Pubkey,
+  pub paused: bool,
+  pub decommissioned: bool,
+  // Rate limits in SPL units per window, zero is unlimited
+  pub window_duration: i64,
+  pub wrap_limit: u64,
+  pub unwrap_limit: u64,
+  pub window_start: i64,
+  pub wrapped_in_window: u64,
+  pub unwrapped_in_window: u64,
+}
+
+impl Wrapper {
+  /// Length of the wrappers created before the extensions were appended
+  pub const LEGACY_LEN: usize = 32 * 4 + 1;
+
+  /// Whether the data only holds the original fields, as legacy wrappers do. They record
+  /// neither their programs nor a bump, charge no fee and have no authority.
+  pub fn is_legacy(&self) -> bool {
+    *self
+      == Wrapper {
+        src20_treasury: self.src20_treasury,
+        src20_token: self.src20_token,
+        spl_treasury: self.spl_treasury,
+        spl_token: self.spl_token,
+        initialized: self.initialized,
+        ..Wrapper::default()
+      }
+  }
+
+  /// Bump seed of the wrapper authority, legacy wrappers derived it from their key alone
+  /// and an empty seed derives the same address
+  pub fn authority_bump(&self) -> Vec<u8> {
+    if self.is_legacy() {
+      return vec![];
+    }
+    vec![self.bump]
+  }
+
+  /// Programs the wrapper calls, the owners of the recorded token accounts stand in for
+  /// the ones a legacy wrapper never recorded
+  pub fn programs(&self, src20_owner: &Pubkey, spl_owner: &Pubkey) -> (Pubkey, Pubkey) {
+    if self.is_legacy() {
+      return (*src20_owner, *spl_owner);
+    }
+    (self.src20_program, self.spl_program)
+  }
+
+  /// The src20 account Wrap opens for an owner who has none yet
+  pub fn find_destination(
+    owner: &Pubkey,
+    src20_token: &Pubkey,
+    program_id: &Pubkey,
+  ) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+      &[
+        DESTINATION_SEED,
+        &owner.to_bytes()[..],
+        &src20_token.to_bytes()[..],
+      ],
+      program_id,
+    )
+  }
+
+  /// Convert an SPL amount, amounts that do not convert back exactly are dust
+  pub fn spl_to_src20(&self, amount: u64) -> Result<u64, AppError> {
+    Self::convert_exact(amount, self.spl_decimals, self.src20_decimals)
+  }
+
+  /// Convert a src20 amount, amounts that do not convert back exactly are dust
+  pub fn src20_to_spl(&self, amount: u64) -> Result<u64, AppError> {
+    Self::convert_exact(amount, self.src20_decimals, self.spl_decimals)
+  }
+
+  /// Convert an SPL amount rounded down, to value reserves rather than to move funds
+  pub fn spl_to_src20_floor(&self, amount: u64) -> Option<u64> {
+    Self::convert(amount, self.spl_decimals, self.src20_decimals).map(|(_, converted)| converted)
+  }
+
+  /// Refuse a fee above what the caller accepted when signing
+  pub fn check_fee(&self, max_fee_bps: u16) -> Result<(), AppError> {
+    if self.fee_bps > max_fee_bps {
+      return Err(AppError::FeeExceeded);
+    }
+    Ok(())
+  }
+
+  /// Fee in bps of an output amount, rounded down
+  pub fn fee(&self, amount: u64) -> Option<u64> {
+    let fee = (amount as u128).checked_mul(self.fee_bps as u128)? / 10_000;
+    fee.try_into().ok()
+  }
+
+  /// Count an SPL amount against the limit of the current window, starting a new one when elapsed.
+  /// A zero window disables the rate limit.
+  pub fn consume_limit(&mut self, now: i64, is_wrap: bool, amount: u64) -> Result<(), AppError> {
+    if self.window_duration == 0 {
+      return Ok(());
+    }
+    if now.saturating_sub(self.window_start) >= self.window_duration {
+      self.window_start = now;
+      self.wrapped_in_window = 0;
+      self.unwrapped_in_window = 0;
+    }
+    let (limit, total) = if is_wrap {
+      (self.wrap_limit, &mut self.wrapped_in_window)
+    } else {
+      (self.unwrap_limit, &mut self.unwrapped_in_window)
+    };
+    *total = total.checked_add(amount).ok_or(AppError::Overflow)?;
+    if limit > 0 && *total > limit {
+      return Err(AppError::RateLimited);
+    }
+    Ok(())
+  }
+
+  /// Refuse amounts that are zero or lose their dust once converted
+  fn convert_exact(amount: u64, from_decimals: u8, to_decimals: u8) -> Result<u64, AppError> {
+    let (used, converted) =
+      Self::convert(amount, from_decimals, to_decimals).ok_or(AppError::Overflow)?;
+    if used != amount || converted == 0 {
+      return Err(AppError::Dust);
+    }
+    Ok(converted)
+  }
+
+  /// Scale up exactly, or floor to the coarser precision leaving the dust unused
+  fn convert(amount: u64, from_decimals: u8, to_decimals: u8) -> Option<(u64, u64)> {
+    if to_decimals >= from_decimals {
+      let factor = 10u64.checked_pow((to_decimals - from_decimals) as u32)?;
+      Some((amount, amount.checked_mul(factor)?))
+    } else {
+      let factor = match 10u64.checked_pow((from_decimals - to_decimals) as u32) {
+        Some(factor) => factor,
+        None => return Some((0, 0)),
+      };
+      let converted = amount / factor;
+      Some((converted * factor, converted))
+    }
+  }
 }
 
 //
@@ -36,13 +194,28 @@ impl IsInitialized for Wrapper {
 //
 impl Pack for Wrapper {
   // Fixed length
-  const LEN: usize = 32 * 4 + 1;
+  const LEN: usize = Self::LEGACY_LEN + 32 * 2 + 1 + 1 + 1 + 32 + 2 + 32 + 32 + 32 + 1 + 1 + 8 * 6;
+  // Legacy wrappers are shorter, their extensions read as default
+  fn unpack_unchecked(input: &[u8]) -> Result<Self, ProgramError> {
+    if input.len() != Self::LEN && input.len() != Self::LEGACY_LEN {
+      return Err(ProgramError::InvalidAccountData);
+    }
+    Self::unpack_from_slice(input)
+  }
+  // Legacy wrappers cannot store extensions, they are never dropped silently
+  fn pack(src: Self, dst: &mut [u8]) -> Result<(), ProgramError> {
+    if dst.len() != Self::LEN && !(dst.len() == Self::LEGACY_LEN && src.is_legacy()) {
+      return Err(ProgramError::InvalidAccountData);
+    }
+    src.pack_into_slice(dst);
+    Ok(())
+  }
   // Unpack data from [u8] to the data struct
   fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-    let src = array_ref![src, 0, 129];
+    let legacy = array_ref![src, 0, 129];
     let (src20_treasury, src20_token, spl_treasury, spl_token, initialized) =
-      array_refs![src, 32, 32, 32, 32, 1];
-    Ok(Wrapper {
+      array_refs![legacy, 32, 32, 32, 32, 1];
+    let wrapper = Wrapper {
       src20_treasury: Pubkey::new_from_array(*src20_treasury),
       src20_token: Pubkey::new_from_array(*src20_token),
       spl_treasury: Pubkey::new_from_array(*spl_treasury),
@@ -52,24 +225,322 @@ impl Pack for Wrapper {
         [1] => true,
         _ => return Err(ProgramError::InvalidAccountData),
       },
+      ..Wrapper::default()
+    };
+    if src.len() == Self::LEGACY_LEN {
+      return Ok(wrapper);
+    }
+    let extensions = array_ref![src, 129, 247];
+    let (
+      src20_program,
+      spl_program,
+      bump,
+      spl_decimals,
+      src20_decimals,
+      fee_authority,
+      fee_bps,
+      src20_fee_vault,
+      spl_fee_vault,
+      authority,
+      paused,
+      decommissioned,
+      window_duration,
+      wrap_limit,
+      unwrap_limit,
+      window_start,
+      wrapped_in_window,
+      unwrapped_in_window,
+    ) = array_refs![extensions, 32, 32, 1, 1, 1, 32, 2, 32, 32, 32, 1, 1, 8, 8, 8, 8, 8, 8];
+    Ok(Wrapper {
+      src20_program: Pubkey::new_from_array(*src20_program),
+      spl_program: Pubkey::new_from_array(*spl_program),
+      bump: u8::from_le_bytes(*bump),
+      spl_decimals: u8::from_le_bytes(*spl_decimals),
+      src20_decimals: u8::from_le_bytes(*src20_decimals),
+      fee_authority: Pubkey::new_from_array(*fee_authority),
+      fee_bps: u16::from_le_bytes(*fee_bps),
+      src20_fee_vault: Pubkey::new_from_array(*src20_fee_vault),
+      spl_fee_vault: Pubkey::new_from_array(*spl_fee_vault),
+      authority: Pubkey::new_from_array(*authority),
+      paused: match paused {
+        [0] => false,
+        [1] => true,
+        _ => return Err(ProgramError::InvalidAccountData),
+      },
+      decommissioned: match decommissioned {
+        [0] => false,
+        [1] => true,
+        _ => return Err(ProgramError::InvalidAccountData),
+      },
+      window_duration: i64::from_le_bytes(*window_duration),
+      wrap_limit: u64::from_le_bytes(*wrap_limit),
+      unwrap_limit: u64::from_le_bytes(*unwrap_limit),
+      window_start: i64::from_le_bytes(*window_start),
+      wrapped_in_window: u64::from_le_bytes(*wrapped_in_window),
+      unwrapped_in_window: u64::from_le_bytes(*unwrapped_in_window),
+      ..wrapper
     })
   }
   // Pack data from the data struct to [u8]
   fn pack_into_slice(&self, dst: &mut [u8]) {
-    let dst = array_mut_ref![dst, 0, 129];
+    let len = dst.len();
+    let legacy = array_mut_ref![dst, 0, 129];
     let (dst_src20_treasury, dst_src20_token, dst_spl_treasury, dst_spl_token, dst_initialized) =
-      mut_array_refs![dst, 32, 32, 32, 32, 1];
+      mut_array_refs![legacy, 32, 32, 32, 32, 1];
     let &Wrapper {
       ref src20_treasury,
       ref src20_token,
       ref spl_treasury,
       ref spl_token,
       initialized,
+      ref src20_program,
+      ref spl_program,
+      bump,
+      spl_decimals,
+      src20_decimals,
+      ref fee_authority,
+      fee_bps,
+      ref src20_fee_vault,
+      ref spl_fee_vault,
+      ref authority,
+      paused,
+      decommissioned,
+      window_duration,
+      wrap_limit,
+      unwrap_limit,
+      window_start,
+      wrapped_in_window,
+      unwrapped_in_window,
     } = self;
     dst_src20_treasury.copy_from_slice(src20_treasury.as_ref());
     dst_src20_token.copy_from_slice(src20_token.as_ref());
     dst_spl_treasury.copy_from_slice(spl_treasury.as_ref());
     dst_spl_token.copy_from_slice(spl_token.as_ref());
     *dst_initialized = [initialized as u8];
+    if len == Self::LEGACY_LEN {
+      return;
+    }
+    let extensions = array_mut_ref![dst, 129, 247];
+    let (
+      dst_src20_program,
+      dst_spl_program,
+      dst_bump,
+      dst_spl_decimals,
+      dst_src20_decimals,
+      dst_fee_authority,
+      dst_fee_bps,
+      dst_src20_fee_vault,
+      dst_spl_fee_vault,
+      dst_authority,
+      dst_paused,
+      dst_decommissioned,
+      dst_window_duration,
+      dst_wrap_limit,
+      dst_unwrap_limit,
+      dst_window_start,
+      dst_wrapped_in_window,
+      dst_unwrapped_in_window,
+    ) = mut_array_refs![extensions, 32, 32, 1, 1, 1, 32, 2, 32, 32, 32, 1, 1, 8, 8, 8, 8, 8, 8];
+    dst_src20_program.copy_from_slice(src20_program.as_ref());
+    dst_spl_program.copy_from_slice(spl_program.as_ref());
+    *dst_bump = bump.to_le_bytes();
+    *dst_spl_decimals = spl_decimals.to_le_bytes();
+    *dst_src20_decimals = src20_decimals.to_le_bytes();
+    dst_fee_authority.copy_from_slice(fee_authority.as_ref());
+    *dst_fee_bps = fee_bps.to_le_bytes();
+    dst_src20_fee_vault.copy_from_slice(src20_fee_vault.as_ref());
+    dst_spl_fee_vault.copy_from_slice(spl_fee_vault.as_ref());
+    dst_authority.copy_from_slice(authority.as_ref());
+    *dst_paused = [paused as u8];
+    *dst_decommissioned = [decommissioned as u8];
+    *dst_window_duration = window_duration.to_le_bytes();
+    *dst_wrap_limit = wrap_limit.to_le_bytes();
+    *dst_unwrap_limit = unwrap_limit.to_le_bytes();
+    *dst_window_start = window_start.to_le_bytes();
+    *dst_wrapped_in_window = wrapped_in_window.to_le_bytes();
+    *dst_unwrapped_in_window = unwrapped_in_window.to_le_bytes();
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn wrapper(spl_decimals: u8, src20_decimals: u8) -> Wrapper {
+    Wrapper {
+      spl_decimals,
+      src20_decimals,
+      initialized: true,
+      ..Wrapper::default()
+    }
+  }
+
+  #[test]
+  fn converts_exact_amounts() {
+    let wrapper = wrapper(9, 6);
+    assert_eq!(wrapper.spl_to_src20(1_500_000_000), Ok(1_500_000));
+    assert_eq!(wrapper.src20_to_spl(1_500_000), Ok(1_500_000_000));
+  }
+
+  #[test]
+  fn refuses_dust_both_ways() {
+    // Scaling down loses the dust
+    let wrapper = wrapper(9, 6);
+    assert_eq!(wrapper.spl_to_src20(1_500_000_001), Err(AppError::Dust));
+    assert_eq!(wrapper.spl_to_src20(999), Err(AppError::Dust));
+    assert_eq!(wrapper.spl_to_src20(0), Err(AppError::Dust));
+    // The other way round
+    let wrapper = Wrapper {
+      spl_decimals: 6,
+      src20_decimals: 9,
+      ..wrapper
+    };
+    assert_eq!(wrapper.src20_to_spl(1_500_000_001), Err(AppError::Dust));
+    assert_eq!(wrapper.spl_to_src20(1_500_000), Ok(1_500_000_000));
+  }
+
+  #[test]
+  fn values_reserves_rounded_down() {
+    let wrapper = wrapper(9, 6);
+    assert_eq!(wrapper.spl_to_src20_floor(1_500_000_001), Some(1_500_000));
+    assert_eq!(wrapper.spl_to_src20_floor(u64::MAX), Some(u64::MAX / 1_000));
+  }
+
+  #[test]
+  fn refuses_fees_above_the_accepted_maximum() {
+    let wrapper = Wrapper {
+      fee_bps: 30,
+      ..wrapper(9, 9)
+    };
+    assert_eq!(wrapper.check_fee(30), Ok(()));
+    assert_eq!(wrapper.check_fee(MAX_FEE_BPS), Ok(()));
+    assert_eq!(wrapper.check_fee(29), Err(AppError::FeeExceeded));
+    assert_eq!(wrapper.fee(1_000_000), Some(3_000));
+  }
+
+  fn limited(window_duration: i64, wrap_limit: u64, unwrap_limit: u64) -> Wrapper {
+    Wrapper {
+      window_start: 1000,
+      window_duration,
+      wrap_limit,
+      unwrap_limit,
+      initialized: true,
+      ..Wrapper::default()
+    }
+  }
+
+  #[test]
+  fn limits_within_a_window() {
+    let mut wrapper = limited(3600, 100, 50);
+    assert_eq!(wrapper.consume_limit(1000, true, 60), Ok(()));
+    assert_eq!(wrapper.consume_limit(1500, true, 40), Ok(()));
+    assert_eq!(
+      wrapper.consume_limit(2000, true, 1),
+      Err(AppError::RateLimited)
+    );
+    // Unwraps are counted apart
+    assert_eq!(wrapper.consume_limit(2000, false, 50), Ok(()));
+    assert_eq!(
+      wrapper.consume_limit(2000, false, 1),
+      Err(AppError::RateLimited)
+    );
+  }
+
+  #[test]
+  fn rolls_the_window_over_once_elapsed() {
+    let mut wrapper = limited(3600, 100, 100);
+    assert_eq!(wrapper.consume_limit(1000, true, 100), Ok(()));
+    // One second short of the window end
+    assert_eq!(
+      wrapper.consume_limit(4599, true, 1),
+      Err(AppError::RateLimited)
+    );
+    let mut wrapper = limited(3600, 100, 100);
+    assert_eq!(wrapper.consume_limit(1000, true, 100), Ok(()));
+    assert_eq!(wrapper.consume_limit(1000, false, 30), Ok(()));
+    assert_eq!(wrapper.consume_limit(4600, true, 100), Ok(()));
+    assert_eq!(wrapper.window_start, 4600);
+    assert_eq!(wrapper.wrapped_in_window, 100);
+    assert_eq!(wrapper.unwrapped_in_window, 0);
+  }
+
+  #[test]
+  fn zero_window_disables_the_limit() {
+    let mut wrapper = limited(0, 100, 100);
+    assert_eq!(wrapper.consume_limit(1000, true, 100), Ok(()));
+    assert_eq!(wrapper.consume_limit(1000, true, 100), Ok(()));
+    assert_eq!(wrapper.wrapped_in_window, 0);
+  }
+
+  #[test]
+  fn zero_limit_is_unlimited() {
+    let mut wrapper = limited(3600, 0, 0);
+    assert_eq!(wrapper.consume_limit(1000, true, u64::MAX), Ok(()));
+    assert_eq!(wrapper.consume_limit(1000, false, u64::MAX), Ok(()));
+  }
+
+  #[test]
+  fn legacy_wrappers_keep_their_layout() {
+    let wrapper = Wrapper {
+      src20_treasury: Pubkey::new_unique(),
+      src20_token: Pubkey::new_unique(),
+      spl_treasury: Pubkey::new_unique(),
+      spl_token: Pubkey::new_unique(),
+      initialized: true,
+      ..Wrapper::default()
+    };
+    let mut legacy = [0u8; Wrapper::LEGACY_LEN];
+    Wrapper::pack(wrapper, &mut legacy).unwrap();
+    let mut current = [0u8; Wrapper::LEN];
+    Wrapper::pack(wrapper, &mut current).unwrap();
+    assert_eq!(legacy[..], current[..Wrapper::LEGACY_LEN]);
+    assert_eq!(Wrapper::unpack(&legacy).unwrap(), wrapper);
+    assert_eq!(Wrapper::unpack(&current).unwrap(), wrapper);
+  }
+
+  #[test]
+  fn legacy_wrappers_reject_extensions() {
+    let wrapper = Wrapper {
+      initialized: true,
+      fee_bps: 30,
+      ..Wrapper::default()
+    };
+    let mut legacy = [0u8; Wrapper::LEGACY_LEN];
+    assert_eq!(
+      Wrapper::pack(wrapper, &mut legacy),
+      Err(ProgramError::InvalidAccountData)
+    );
+  }
+
+  #[test]
+  fn legacy_wrappers_derive_their_authority_without_bump() {
+    let program_id = Pubkey::new_unique();
+    let wrapper_key = Pubkey::new_unique();
+    let legacy = Wrapper {
+      initialized: true,
+      ..Wrapper::default()
+    };
+    let seed: &[&[_]] = &[&wrapper_key.to_bytes()[..], &legacy.authority_bump()[..]];
+    assert_eq!(
+      Pubkey::create_program_address(seed, &program_id),
+      Pubkey::create_program_address(&[&wrapper_key.to_bytes()[..]], &program_id)
+    );
+    let src20_owner = Pubkey::new_unique();
+    let spl_owner = Pubkey::new_unique();
+    assert_eq!(
+      legacy.programs(&src20_owner, &spl_owner),
+      (src20_owner, spl_owner)
+    );
+    let (_, bump) = Pubkey::find_program_address(&[&wrapper_key.to_bytes()[..]], &program_id);
+    let current = Wrapper {
+      src20_program: Pubkey::new_unique(),
+      bump,
+      ..legacy
+    };
+    assert_eq!(current.authority_bump(), vec![bump]);
+    assert_eq!(
+      current.programs(&src20_owner, &spl_owner).0,
+      current.src20_program
+    );
   }
 }